@@ -0,0 +1,143 @@
+use crate::graph::GraphError;
+use petgraph::algo::toposort;
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::visit::NodeIndexable;
+use petgraph::{Directed, Direction};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// breadth first walk in one direction, returning every node reached with its hop
+/// distance, the start node itself is not included
+pub fn reachable_within<N, E>(
+    graph: &StableGraph<N, E, Directed>,
+    start: NodeIndex,
+    direction: Direction,
+    max_depth: Option<u32>,
+) -> Vec<(NodeIndex, u32)> {
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([(start, 0u32)]);
+    let mut found = Vec::new();
+    while let Some((node, depth)) = queue.pop_front() {
+        if max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+        for next in graph.neighbors_directed(node, direction) {
+            if seen.insert(next) {
+                found.push((next, depth + 1));
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+    found
+}
+
+/// answers lowest common ancestor queries, using binary lifting when every node has
+/// at most one parent and falling back to ancestor set intersection for general dags
+pub struct AncestorIndex<'a, N, E> {
+    graph: &'a StableGraph<N, E, Directed>,
+    level: Vec<u32>,
+    lifting: Option<Vec<Vec<Option<NodeIndex>>>>,
+    ancestor_cache: HashMap<NodeIndex, HashSet<NodeIndex>>,
+}
+
+impl<'a, N, E> AncestorIndex<'a, N, E> {
+    pub fn new(graph: &'a StableGraph<N, E, Directed>) -> Result<Self, GraphError> {
+        let order = toposort(graph, None).map_err(|_| {
+            GraphError::InvalidStructure("lowest common ancestor requires an acyclic graph".into())
+        })?;
+
+        let bound = graph.node_bound();
+        let mut level = vec![0u32; bound];
+        let mut parent = vec![None; bound];
+        let mut is_forest = true;
+        for &node in &order {
+            let mut parents = graph.neighbors_directed(node, Direction::Incoming);
+            parent[node.index()] = parents.next();
+            if parents.next().is_some() {
+                is_forest = false;
+            }
+            for child in graph.neighbors_directed(node, Direction::Outgoing) {
+                level[child.index()] = level[child.index()].max(level[node.index()] + 1);
+            }
+        }
+
+        let lifting = is_forest.then(|| {
+            let height = level.iter().max().copied().unwrap_or(0).max(1);
+            let steps = (u32::BITS - height.leading_zeros()) as usize;
+            let mut lifting = vec![parent];
+            for k in 1..steps {
+                let next = lifting[k - 1]
+                    .iter()
+                    .map(|p: &Option<NodeIndex>| p.and_then(|p| lifting[k - 1][p.index()]))
+                    .collect();
+                lifting.push(next);
+            }
+            lifting
+        });
+
+        Ok(AncestorIndex {
+            graph,
+            level,
+            lifting,
+            ancestor_cache: HashMap::new(),
+        })
+    }
+
+    pub fn lowest_common_ancestor(&mut self, a: NodeIndex, b: NodeIndex) -> Option<NodeIndex> {
+        match &self.lifting {
+            Some(lifting) => lift_to_common(lifting, &self.level, a, b),
+            None => self.deepest_shared_ancestor(a, b),
+        }
+    }
+
+    /// the shared ancestor (or self) with the greatest level is never an ancestor
+    /// of another shared ancestor, so it is a valid lowest common ancestor
+    fn deepest_shared_ancestor(&mut self, a: NodeIndex, b: NodeIndex) -> Option<NodeIndex> {
+        let graph = self.graph;
+        let ancestors_of_a = self.ancestor_cache.entry(a).or_insert_with(|| {
+            reachable_within(graph, a, Direction::Incoming, None)
+                .into_iter()
+                .map(|(n, _)| n)
+                .chain([a])
+                .collect()
+        });
+        reachable_within(graph, b, Direction::Incoming, None)
+            .into_iter()
+            .map(|(n, _)| n)
+            .chain([b])
+            .filter(|n| ancestors_of_a.contains(n))
+            .max_by_key(|n| (self.level[n.index()], std::cmp::Reverse(n.index())))
+    }
+}
+
+fn lift_to_common(
+    lifting: &[Vec<Option<NodeIndex>>],
+    level: &[u32],
+    mut a: NodeIndex,
+    mut b: NodeIndex,
+) -> Option<NodeIndex> {
+    if level[a.index()] < level[b.index()] {
+        std::mem::swap(&mut a, &mut b);
+    }
+    let mut gap = level[a.index()] - level[b.index()];
+    let mut k = 0;
+    while gap > 0 {
+        if gap & 1 == 1 {
+            a = lifting[k][a.index()]?;
+        }
+        gap >>= 1;
+        k += 1;
+    }
+    if a == b {
+        return Some(a);
+    }
+    for jumps in lifting.iter().rev() {
+        if let (Some(up_a), Some(up_b)) = (jumps[a.index()], jumps[b.index()]) {
+            if up_a != up_b {
+                a = up_a;
+                b = up_b;
+            }
+        }
+    }
+    let (parent_a, parent_b) = (lifting[0][a.index()]?, lifting[0][b.index()]?);
+    (parent_a == parent_b).then_some(parent_a)
+}
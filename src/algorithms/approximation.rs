@@ -0,0 +1,171 @@
+use super::distance::{check_non_negative, ShortestPathTree, Step};
+use crate::graph::GraphError;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex, StableGraph};
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::NodeIndexable;
+use petgraph::Undirected;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// shortest path trees from every terminal, i.e. the metric closure restricted to
+/// the terminals, failing when two terminals are not connected
+fn metric_closure<N>(
+    graph: &StableGraph<N, f64, Undirected>,
+    terminals: &[NodeIndex],
+) -> Result<Vec<ShortestPathTree>, GraphError> {
+    check_non_negative(graph)?;
+    let trees: Vec<ShortestPathTree> = terminals
+        .iter()
+        .map(|&t| ShortestPathTree::new(graph, t))
+        .collect();
+    if trees
+        .iter()
+        .any(|tree| terminals.iter().any(|t| !tree.distance.contains_key(t)))
+    {
+        return Err(GraphError::InvalidStructure(
+            "terminal nodes are not all connected".into(),
+        ));
+    }
+    Ok(trees)
+}
+
+/// prim's algorithm on the complete graph over the (non-empty) terminals, returning
+/// the parent of every terminal position with the first terminal as the root
+fn closure_spanning_tree(
+    terminals: &[NodeIndex],
+    trees: &[ShortestPathTree],
+) -> Vec<Option<usize>> {
+    let count = terminals.len();
+    let mut parent = vec![None; count];
+    let mut best = vec![f64::INFINITY; count];
+    let mut done = vec![false; count];
+    best[0] = 0.0;
+    for _ in 0..count {
+        let current = (0..count)
+            .filter(|&i| !done[i])
+            .min_by(|&a, &b| best[a].total_cmp(&best[b]))
+            .expect("an unfinished terminal remains");
+        done[current] = true;
+        for next in (0..count).filter(|&i| !done[i]) {
+            let cost = trees[current].distance[&terminals[next]];
+            if cost < best[next] {
+                best[next] = cost;
+                parent[next] = Some(current);
+            }
+        }
+    }
+    parent
+}
+
+/// tour visiting every terminal and returning to the first, from a preorder walk of
+/// the metric closure's minimum spanning tree (at most twice the optimal length)
+pub fn tsp_tour<N>(
+    graph: &StableGraph<N, f64, Undirected>,
+    terminals: &[NodeIndex],
+) -> Result<Vec<Step>, GraphError> {
+    let mut terminals = terminals.to_vec();
+    let mut seen = HashSet::new();
+    terminals.retain(|t| seen.insert(*t));
+    if terminals.len() < 2 {
+        return Ok(Vec::new());
+    }
+    let trees = metric_closure(graph, &terminals)?;
+    let parent = closure_spanning_tree(&terminals, &trees);
+
+    let mut children = vec![Vec::new(); terminals.len()];
+    for (child, p) in parent.iter().enumerate() {
+        if let Some(p) = p {
+            children[*p].push(child);
+        }
+    }
+    let mut order = Vec::with_capacity(terminals.len());
+    let mut stack = vec![0usize];
+    while let Some(position) = stack.pop() {
+        order.push(position);
+        stack.extend(children[position].iter().rev());
+    }
+    order.push(0);
+
+    Ok(order
+        .windows(2)
+        .flat_map(|pair| {
+            trees[pair[0]]
+                .path_to(terminals[pair[1]])
+                .expect("terminals are connected")
+        })
+        .collect())
+}
+
+/// steiner tree connecting the terminals with the metric closure heuristic (kou, markowsky
+/// and berman): expand the closure's spanning tree into graph paths, take a spanning
+/// tree of the union and prune leaves that are not terminals
+pub fn steiner_tree<N>(
+    graph: &StableGraph<N, f64, Undirected>,
+    terminals: &[NodeIndex],
+) -> Result<Vec<Step>, GraphError> {
+    let mut terminals = terminals.to_vec();
+    let mut seen = HashSet::new();
+    terminals.retain(|t| seen.insert(*t));
+    if terminals.is_empty() {
+        return Ok(Vec::new());
+    }
+    let trees = metric_closure(graph, &terminals)?;
+    let parent = closure_spanning_tree(&terminals, &trees);
+
+    let mut candidate_edges: Vec<EdgeIndex> = parent
+        .iter()
+        .enumerate()
+        .filter_map(|(child, p)| p.map(|p| (p, child)))
+        .flat_map(|(p, child)| {
+            trees[p]
+                .path_to(terminals[child])
+                .expect("terminals are connected")
+        })
+        .map(|(_, _, e)| e)
+        .collect();
+    candidate_edges.sort();
+    candidate_edges.dedup();
+    candidate_edges.sort_by(|&a, &b| graph[a].total_cmp(&graph[b]));
+
+    let mut components = UnionFind::<usize>::new(graph.node_bound());
+    let mut kept: Vec<Step> = candidate_edges
+        .into_iter()
+        .filter_map(|e| {
+            let (a, b) = graph.edge_endpoints(e).expect("edge exists");
+            components.union(a.index(), b.index()).then_some((a, b, e))
+        })
+        .collect();
+
+    let required: HashSet<NodeIndex> = terminals.iter().copied().collect();
+    loop {
+        let mut degree: HashMap<NodeIndex, usize> = HashMap::new();
+        for &(a, b, _) in &kept {
+            *degree.entry(a).or_default() += 1;
+            *degree.entry(b).or_default() += 1;
+        }
+        let is_spare_leaf = |n: &NodeIndex| degree[n] == 1 && !required.contains(n);
+        let before = kept.len();
+        kept.retain(|(a, b, _)| !is_spare_leaf(a) && !is_spare_leaf(b));
+        if kept.len() == before {
+            break;
+        }
+    }
+
+    // hand the edges back outward from the first terminal, each oriented parent to child
+    let mut adjacency: HashMap<NodeIndex, Vec<(NodeIndex, EdgeIndex)>> = HashMap::new();
+    for &(a, b, e) in &kept {
+        adjacency.entry(a).or_default().push((b, e));
+        adjacency.entry(b).or_default().push((a, e));
+    }
+    let mut ordered = Vec::with_capacity(kept.len());
+    let mut visited = HashSet::from([terminals[0]]);
+    let mut queue = VecDeque::from([terminals[0]]);
+    while let Some(node) = queue.pop_front() {
+        for &(next, e) in adjacency.get(&node).into_iter().flatten() {
+            if visited.insert(next) {
+                ordered.push((node, next, e));
+                queue.push_back(next);
+            }
+        }
+    }
+    Ok(ordered)
+}
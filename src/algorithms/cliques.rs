@@ -0,0 +1,137 @@
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::Undirected;
+use std::collections::{BTreeSet, HashMap};
+
+pub(crate) type NodeSet = BTreeSet<NodeIndex>;
+
+/// neighbour sets without self loops or parallel edges, as cliques only care about adjacency
+pub(crate) fn neighbour_sets<N, E>(
+    graph: &StableGraph<N, E, Undirected>,
+) -> HashMap<NodeIndex, NodeSet> {
+    graph
+        .node_indices()
+        .map(|n| (n, graph.neighbors(n).filter(|&m| m != n).collect()))
+        .collect()
+}
+
+struct BronKerbosch<'a> {
+    neighbours: &'a HashMap<NodeIndex, NodeSet>,
+    min_size: usize,
+    limit: Option<usize>,
+    cliques: Vec<Vec<NodeIndex>>,
+}
+
+impl BronKerbosch<'_> {
+    fn is_full(&self) -> bool {
+        self.limit.is_some_and(|limit| self.cliques.len() >= limit)
+    }
+
+    /// pivot on the node covering most candidates so only its non-neighbours branch
+    fn pivot(&self, candidates: &NodeSet, excluded: &NodeSet) -> Option<NodeIndex> {
+        candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|u| self.neighbours[u].intersection(candidates).count())
+            .copied()
+    }
+
+    fn expand(
+        &mut self,
+        clique: &mut Vec<NodeIndex>,
+        mut candidates: NodeSet,
+        mut excluded: NodeSet,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() >= self.min_size {
+                let mut found = clique.clone();
+                found.sort();
+                self.cliques.push(found);
+            }
+            return;
+        }
+        let pivot_neighbours = self
+            .pivot(&candidates, &excluded)
+            .map(|u| self.neighbours[&u].clone())
+            .unwrap_or_default();
+        let branches: Vec<NodeIndex> = candidates.difference(&pivot_neighbours).copied().collect();
+        for node in branches {
+            if self.is_full() {
+                return;
+            }
+            let neighbours = &self.neighbours[&node];
+            clique.push(node);
+            self.expand(
+                clique,
+                candidates.intersection(neighbours).copied().collect(),
+                excluded.intersection(neighbours).copied().collect(),
+            );
+            clique.pop();
+            candidates.remove(&node);
+            excluded.insert(node);
+        }
+    }
+}
+
+/// enumerates maximal cliques with bron-kerbosch and pivoting, keeping those with at
+/// least `min_size` members and stopping once `limit` cliques have been found
+pub fn maximal_cliques<N, E>(
+    graph: &StableGraph<N, E, Undirected>,
+    min_size: usize,
+    limit: Option<usize>,
+) -> Vec<Vec<NodeIndex>> {
+    let neighbours = neighbour_sets(graph);
+    let mut search = BronKerbosch {
+        neighbours: &neighbours,
+        min_size,
+        limit,
+        cliques: Vec::new(),
+    };
+    search.expand(
+        &mut Vec::new(),
+        graph.node_indices().collect(),
+        NodeSet::new(),
+    );
+    search.cliques
+}
+
+/// branch and bound over shrinking candidate sets, dropping any branch that cannot
+/// outgrow the best clique found so far
+pub fn maximum_clique<N, E>(graph: &StableGraph<N, E, Undirected>) -> Vec<NodeIndex> {
+    fn grow(
+        neighbours: &HashMap<NodeIndex, NodeSet>,
+        clique: &mut Vec<NodeIndex>,
+        mut candidates: NodeSet,
+        best: &mut Vec<NodeIndex>,
+    ) {
+        if candidates.is_empty() {
+            if clique.len() > best.len() {
+                *best = clique.clone();
+            }
+            return;
+        }
+        while let Some(&node) = candidates.iter().next() {
+            if clique.len() + candidates.len() <= best.len() {
+                return;
+            }
+            clique.push(node);
+            let next = candidates
+                .intersection(&neighbours[&node])
+                .copied()
+                .collect();
+            grow(neighbours, clique, next, best);
+            clique.pop();
+            candidates.remove(&node);
+        }
+    }
+
+    let neighbours = neighbour_sets(graph);
+    let mut best = Vec::new();
+    grow(
+        &neighbours,
+        &mut Vec::new(),
+        graph.node_indices().collect(),
+        &mut best,
+    );
+    best.sort();
+    best
+}
@@ -0,0 +1,89 @@
+use super::distance::check_non_negative;
+use crate::graph::GraphError;
+use ordered_float::OrderedFloat;
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences, NodeIndexable};
+use petgraph::Undirected;
+use std::collections::{BinaryHeap, HashMap};
+
+/// global minimum cut with stoer-wagner: every phase grows a maximum adjacency order,
+/// the last node added is cut off from the rest at the weight it was attached with,
+/// then the last two nodes are merged. returns the cut weight and the smaller side
+pub fn minimum_cut<N>(
+    graph: &StableGraph<N, f64, Undirected>,
+) -> Result<(f64, Vec<NodeIndex>), GraphError> {
+    check_non_negative(graph)?;
+    if graph.node_count() < 2 {
+        return Err(GraphError::InvalidStructure(
+            "a cut needs at least two nodes".into(),
+        ));
+    }
+
+    let bound = graph.node_bound();
+    let mut members: Vec<Vec<NodeIndex>> = (0..bound).map(|i| vec![NodeIndex::new(i)]).collect();
+    let mut adjacency: Vec<HashMap<usize, f64>> = vec![HashMap::new(); bound];
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a != b {
+            *adjacency[a].entry(b).or_default() += edge.weight();
+            *adjacency[b].entry(a).or_default() += edge.weight();
+        }
+    }
+    let mut active: Vec<usize> = graph.node_indices().map(|n| n.index()).collect();
+
+    let mut best = (f64::INFINITY, Vec::new());
+    let mut key = vec![0.0; bound];
+    let mut added = vec![false; bound];
+    while active.len() > 1 {
+        for &node in &active {
+            key[node] = 0.0;
+            added[node] = false;
+        }
+        let mut heap: BinaryHeap<(OrderedFloat<f64>, usize)> =
+            active.iter().map(|&n| (OrderedFloat(0.0), n)).collect();
+        let (mut previous, mut last) = (usize::MAX, usize::MAX);
+        while let Some((OrderedFloat(weight), node)) = heap.pop() {
+            // entries are pushed on every increase, so older ones carry a smaller key
+            if added[node] || weight < key[node] {
+                continue;
+            }
+            added[node] = true;
+            (previous, last) = (last, node);
+            for (&next, &w) in &adjacency[node] {
+                if !added[next] {
+                    key[next] += w;
+                    heap.push((OrderedFloat(key[next]), next));
+                }
+            }
+        }
+
+        if key[last] < best.0 {
+            best = (key[last], members[last].clone());
+        }
+        let merged = std::mem::take(&mut adjacency[last]);
+        for (next, w) in merged {
+            adjacency[next].remove(&last);
+            if next != previous {
+                *adjacency[previous].entry(next).or_default() += w;
+                *adjacency[next].entry(previous).or_default() += w;
+            }
+        }
+        let absorbed = std::mem::take(&mut members[last]);
+        members[previous].extend(absorbed);
+        active.retain(|&n| n != last);
+    }
+
+    let (value, mut side) = best;
+    if side.len() * 2 > graph.node_count() {
+        let mut inside = vec![false; bound];
+        for node in &side {
+            inside[node.index()] = true;
+        }
+        side = graph
+            .node_indices()
+            .filter(|n| !inside[n.index()])
+            .collect();
+    }
+    side.sort();
+    Ok((value, side))
+}
@@ -0,0 +1,166 @@
+use petgraph::algo::tarjan_scc;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex, StableGraph};
+use petgraph::visit::{EdgeRef, NodeFiltered, NodeIndexable};
+use petgraph::{Directed, Undirected};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// fundamental cycles of an undirected graph: every edge left out of a breadth first
+/// spanning forest closes exactly one cycle through the forest
+pub fn cycle_basis<N, E>(graph: &StableGraph<N, E, Undirected>) -> Vec<Vec<NodeIndex>> {
+    let bound = graph.node_bound();
+    let mut parent: Vec<Option<NodeIndex>> = vec![None; bound];
+    let mut depth = vec![0usize; bound];
+    let mut seen = vec![false; bound];
+    let mut tree_edges: HashSet<EdgeIndex> = HashSet::new();
+
+    for root in graph.node_indices() {
+        if std::mem::replace(&mut seen[root.index()], true) {
+            continue;
+        }
+        let mut queue = VecDeque::from([root]);
+        while let Some(node) = queue.pop_front() {
+            for edge in graph.edges(node) {
+                let next = edge.target();
+                if !std::mem::replace(&mut seen[next.index()], true) {
+                    parent[next.index()] = Some(node);
+                    depth[next.index()] = depth[node.index()] + 1;
+                    tree_edges.insert(edge.id());
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    let mut edges: Vec<EdgeIndex> = graph
+        .edge_indices()
+        .filter(|e| !tree_edges.contains(e))
+        .collect();
+    edges.sort();
+    edges
+        .into_iter()
+        .map(|edge| {
+            let (mut a, mut b) = graph.edge_endpoints(edge).expect("edge exists");
+            // climb both ends to their meeting point, keeping the cycle in walk order
+            let (mut left, mut right) = (vec![a], vec![b]);
+            while a != b {
+                if depth[a.index()] >= depth[b.index()] {
+                    a = parent[a.index()].expect("non-root node has a parent");
+                    left.push(a);
+                } else {
+                    b = parent[b.index()].expect("non-root node has a parent");
+                    right.push(b);
+                }
+            }
+            right.pop();
+            left.extend(right.into_iter().rev());
+            left
+        })
+        .collect()
+}
+
+/// elementary cycles of a directed graph with johnson's algorithm, each cycle starts
+/// at its smallest node and the search stops after `limit` cycles
+pub fn simple_cycles<N, E>(
+    graph: &StableGraph<N, E, Directed>,
+    limit: Option<usize>,
+) -> Vec<Vec<NodeIndex>> {
+    let full = |cycles: &Vec<Vec<NodeIndex>>| limit.is_some_and(|limit| cycles.len() >= limit);
+    let mut cycles: Vec<Vec<NodeIndex>> = Vec::new();
+
+    // self loops are cycles of their own and only get in the way of the search below
+    for node in graph.node_indices() {
+        if full(&cycles) {
+            return cycles;
+        }
+        if graph.contains_edge(node, node) {
+            cycles.push(vec![node]);
+        }
+    }
+    let successors: HashMap<NodeIndex, Vec<NodeIndex>> = graph
+        .node_indices()
+        .map(|n| {
+            let mut next: Vec<NodeIndex> = graph.neighbors(n).filter(|&m| m != n).collect();
+            next.sort();
+            next.dedup();
+            (n, next)
+        })
+        .collect();
+
+    let mut pending: Vec<HashSet<NodeIndex>> = non_trivial_components(graph, |_| true);
+    while let Some(mut component) = pending.pop() {
+        let start = *component.iter().min().expect("components are non-empty");
+        let within = |n: &NodeIndex| component.contains(n);
+        let neighbours_of = |n: NodeIndex| -> Vec<NodeIndex> {
+            successors[&n].iter().copied().filter(within).collect()
+        };
+
+        let mut path = vec![start];
+        let mut blocked = HashSet::from([start]);
+        let mut closed = HashSet::new();
+        let mut blocked_by: HashMap<NodeIndex, HashSet<NodeIndex>> = HashMap::new();
+        let mut stack = vec![(start, neighbours_of(start))];
+        while let Some((node, neighbours)) = stack.last_mut() {
+            let node = *node;
+            if let Some(next) = neighbours.pop() {
+                if next == start {
+                    cycles.push(path.clone());
+                    if full(&cycles) {
+                        return cycles;
+                    }
+                    closed.extend(path.iter().copied());
+                } else if !blocked.contains(&next) {
+                    path.push(next);
+                    closed.remove(&next);
+                    blocked.insert(next);
+                    stack.push((next, neighbours_of(next)));
+                    continue;
+                }
+            }
+            if stack.last().is_some_and(|(_, n)| n.is_empty()) {
+                if closed.contains(&node) {
+                    unblock(node, &mut blocked, &mut blocked_by);
+                } else {
+                    for next in neighbours_of(node) {
+                        blocked_by.entry(next).or_default().insert(node);
+                    }
+                }
+                stack.pop();
+                path.pop();
+            }
+        }
+
+        component.remove(&start);
+        pending.extend(non_trivial_components(graph, |n| component.contains(&n)));
+    }
+    cycles
+}
+
+fn unblock(
+    node: NodeIndex,
+    blocked: &mut HashSet<NodeIndex>,
+    blocked_by: &mut HashMap<NodeIndex, HashSet<NodeIndex>>,
+) {
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        if blocked.remove(&node) {
+            if let Some(waiting) = blocked_by.get_mut(&node) {
+                stack.extend(waiting.drain());
+            }
+        }
+    }
+}
+
+/// strongly connected components with more than one node among the kept nodes
+fn non_trivial_components<N, E, F>(
+    graph: &StableGraph<N, E, Directed>,
+    keep: F,
+) -> Vec<HashSet<NodeIndex>>
+where
+    F: Fn(NodeIndex) -> bool,
+{
+    tarjan_scc(&NodeFiltered::from_fn(graph, keep))
+        .into_iter()
+        .filter(|scc| scc.len() > 1)
+        .map(|scc| scc.into_iter().collect())
+        .collect()
+}
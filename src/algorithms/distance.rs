@@ -0,0 +1,358 @@
+use crate::graph::GraphError;
+use ordered_float::OrderedFloat;
+use petgraph::algo::dijkstra;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex, StableGraph};
+use petgraph::visit::EdgeRef;
+use petgraph::{Direction, EdgeType};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// one traversed edge, oriented in the direction it was walked
+pub type Step = (NodeIndex, NodeIndex, EdgeIndex);
+
+/// distances from a source to every node it reaches, counting hops or summing weights
+pub fn single_source_distances<N, Ty: EdgeType>(
+    graph: &StableGraph<N, f64, Ty>,
+    source: NodeIndex,
+    weighted: bool,
+) -> HashMap<NodeIndex, f64> {
+    if weighted {
+        return dijkstra(graph, source, None, |e| *e.weight());
+    }
+    let mut distances = HashMap::from([(source, 0.0)]);
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        let next_distance = distances[&node] + 1.0;
+        for next in graph.neighbors(node) {
+            distances.entry(next).or_insert_with(|| {
+                queue.push_back(next);
+                next_distance
+            });
+        }
+    }
+    distances
+}
+
+/// dijkstra and weighted sampling misbehave on negative weights, so refuse them up front
+pub fn check_non_negative<N, Ty: EdgeType>(
+    graph: &StableGraph<N, f64, Ty>,
+) -> Result<(), GraphError> {
+    match graph.edge_weights().find(|w| **w < 0.0 || w.is_nan()) {
+        Some(w) => Err(GraphError::InvalidDataType(format!(
+            "Edge weights must be non-negative, found {}",
+            w
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn farthest(distances: &HashMap<NodeIndex, f64>) -> (NodeIndex, f64) {
+    distances
+        .iter()
+        .map(|(&n, &d)| (n, d))
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        .expect("distances always contain the source")
+}
+
+fn disconnected(directed: bool) -> GraphError {
+    GraphError::InvalidStructure(if directed {
+        "eccentricity is undefined because the graph is not strongly connected".into()
+    } else {
+        "eccentricity is undefined because the graph is disconnected".into()
+    })
+}
+
+/// greatest distance from each node to any other node, failing when some node
+/// cannot reach every other node rather than reporting infinity
+pub fn eccentricities<N, Ty: EdgeType>(
+    graph: &StableGraph<N, f64, Ty>,
+    weighted: bool,
+) -> Result<Vec<(NodeIndex, f64)>, GraphError> {
+    if weighted {
+        check_non_negative(graph)?;
+    }
+    graph
+        .node_indices()
+        .map(|node| {
+            let distances = single_source_distances(graph, node, weighted);
+            if distances.len() < graph.node_count() {
+                return Err(disconnected(graph.is_directed()));
+            }
+            Ok((node, farthest(&distances).1))
+        })
+        .collect()
+}
+
+/// double sweep lower bound on the diameter: the farthest node from an arbitrary
+/// start is swept again and its eccentricity is returned
+pub fn double_sweep_diameter<N, Ty: EdgeType>(
+    graph: &StableGraph<N, f64, Ty>,
+    weighted: bool,
+) -> Result<f64, GraphError> {
+    if weighted {
+        check_non_negative(graph)?;
+    }
+    let start = graph
+        .node_indices()
+        .next()
+        .ok_or_else(|| GraphError::InvalidStructure("graph has no nodes".into()))?;
+    let first = single_source_distances(graph, start, weighted);
+    if first.len() < graph.node_count() {
+        return Err(disconnected(graph.is_directed()));
+    }
+    let (far, _) = farthest(&first);
+    let second = single_source_distances(graph, far, weighted);
+    if second.len() < graph.node_count() {
+        return Err(disconnected(graph.is_directed()));
+    }
+    Ok(farthest(&second).1)
+}
+
+/// weighted distances from a source along with the edge used to reach each node
+pub struct ShortestPathTree {
+    pub source: NodeIndex,
+    pub distance: HashMap<NodeIndex, f64>,
+    via: HashMap<NodeIndex, (NodeIndex, EdgeIndex)>,
+}
+
+impl ShortestPathTree {
+    /// dijkstra from the source, keeping predecessor edges so paths can be rebuilt
+    pub fn new<N, Ty: EdgeType>(graph: &StableGraph<N, f64, Ty>, source: NodeIndex) -> Self {
+        let mut distance = HashMap::from([(source, 0.0)]);
+        let mut via = HashMap::new();
+        let mut heap = BinaryHeap::from([Reverse((OrderedFloat(0.0), source))]);
+        while let Some(Reverse((OrderedFloat(reached), node))) = heap.pop() {
+            if reached > distance[&node] {
+                continue;
+            }
+            for edge in graph.edges(node) {
+                let next = edge.target();
+                let candidate = reached + edge.weight();
+                if distance.get(&next).is_none_or(|&known| candidate < known) {
+                    distance.insert(next, candidate);
+                    via.insert(next, (node, edge.id()));
+                    heap.push(Reverse((OrderedFloat(candidate), next)));
+                }
+            }
+        }
+        ShortestPathTree {
+            source,
+            distance,
+            via,
+        }
+    }
+
+    /// the (from, to, edge) steps leading from the source to the target, if reachable
+    pub fn path_to(&self, target: NodeIndex) -> Option<Vec<Step>> {
+        if !self.distance.contains_key(&target) {
+            return None;
+        }
+        let mut steps = Vec::new();
+        let mut node = target;
+        while node != self.source {
+            let (previous, edge) = self.via[&node];
+            steps.push((previous, node, edge));
+            node = previous;
+        }
+        steps.reverse();
+        Some(steps)
+    }
+}
+
+/// point to point dijkstra growing one search from each end and stopping once the two
+/// frontiers can no longer improve on the best meeting found, returns the length and steps
+pub fn bidirectional_dijkstra<N, Ty: EdgeType>(
+    graph: &StableGraph<N, f64, Ty>,
+    source: NodeIndex,
+    target: NodeIndex,
+) -> Option<(f64, Vec<Step>)> {
+    if source == target {
+        return Some((0.0, Vec::new()));
+    }
+    let directions = [Direction::Outgoing, Direction::Incoming];
+    let mut distance = [
+        HashMap::from([(source, 0.0)]),
+        HashMap::from([(target, 0.0)]),
+    ];
+    let mut via: [HashMap<NodeIndex, (NodeIndex, EdgeIndex)>; 2] = [HashMap::new(), HashMap::new()];
+    let mut settled = [HashSet::new(), HashSet::new()];
+    let mut heaps = [
+        BinaryHeap::from([Reverse((OrderedFloat(0.0), source))]),
+        BinaryHeap::from([Reverse((OrderedFloat(0.0), target))]),
+    ];
+    let mut best: Option<(f64, NodeIndex)> = None;
+
+    loop {
+        let top = |heap: &BinaryHeap<Reverse<(OrderedFloat<f64>, NodeIndex)>>| {
+            heap.peek().map(|Reverse((d, _))| d.into_inner())
+        };
+        let (forward_top, backward_top) = match (top(&heaps[0]), top(&heaps[1])) {
+            (Some(f), Some(b)) => (f, b),
+            _ => break,
+        };
+        if best.is_some_and(|(length, _)| forward_top + backward_top >= length) {
+            break;
+        }
+        let side = if forward_top <= backward_top { 0 } else { 1 };
+        let Reverse((OrderedFloat(reached), node)) = heaps[side].pop().expect("heap is not empty");
+        if !settled[side].insert(node) {
+            continue;
+        }
+        // for both directions the node on the far side of the edge is its source or target
+        for edge in graph.edges_directed(node, directions[side]) {
+            let next = if side == 0 {
+                edge.target()
+            } else {
+                edge.source()
+            };
+            let candidate = reached + edge.weight();
+            if distance[side]
+                .get(&next)
+                .is_none_or(|&known| candidate < known)
+            {
+                distance[side].insert(next, candidate);
+                via[side].insert(next, (node, edge.id()));
+                heaps[side].push(Reverse((OrderedFloat(candidate), next)));
+                if let Some(&other) = distance[1 - side].get(&next) {
+                    if best.is_none_or(|(length, _)| candidate + other < length) {
+                        best = Some((candidate + other, next));
+                    }
+                }
+            }
+        }
+    }
+
+    let (length, meeting) = best?;
+    let mut steps = Vec::new();
+    let mut node = meeting;
+    while let Some(&(previous, edge)) = via[0].get(&node) {
+        steps.push((previous, node, edge));
+        node = previous;
+    }
+    steps.reverse();
+    let mut node = meeting;
+    while let Some(&(next, edge)) = via[1].get(&node) {
+        steps.push((node, next, edge));
+        node = next;
+    }
+    Some((length, steps))
+}
+
+/// per node values steering a point to point search, indexed by node index
+#[derive(Clone, Debug, Default)]
+pub struct NodeCosts {
+    /// estimated remaining cost to the target, an a* heuristic that must not overestimate
+    pub heuristic: Option<Vec<f64>>,
+    /// added whenever a path enters the node, the source's own is not counted
+    pub weights: Option<Vec<f64>>,
+    /// whether a path may use the node at all, endpoints included
+    pub allowed: Option<Vec<bool>>,
+}
+
+impl NodeCosts {
+    pub fn is_empty(&self) -> bool {
+        self.heuristic.is_none() && self.weights.is_none() && self.allowed.is_none()
+    }
+
+    fn value(values: &Option<Vec<f64>>, node: NodeIndex) -> f64 {
+        values
+            .as_ref()
+            .and_then(|v| v.get(node.index()).copied())
+            .unwrap_or(0.0)
+    }
+
+    fn allows(&self, node: NodeIndex) -> bool {
+        self.allowed
+            .as_ref()
+            .is_none_or(|a| a.get(node.index()).copied().unwrap_or(false))
+    }
+
+    /// node weights must be non-negative like edge weights, and no value may be nan
+    pub fn check(&self) -> Result<(), GraphError> {
+        let heuristic = self.heuristic.iter().flatten();
+        if let Some(h) = heuristic.copied().find(|h| h.is_nan()) {
+            return Err(GraphError::InvalidDataType(format!(
+                "Heuristic values must be numbers, found {}",
+                h
+            )));
+        }
+        match self
+            .weights
+            .iter()
+            .flatten()
+            .find(|w| **w < 0.0 || w.is_nan())
+        {
+            Some(w) => Err(GraphError::InvalidDataType(format!(
+                "Node weights must be non-negative, found {}",
+                w
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// a* from source to target, where entering a node costs its node weight on top of the
+/// edge weight and disallowed nodes are never entered. nodes are reopened when a cheaper
+/// way in turns up, so a heuristic that is admissible but not consistent stays exact
+pub fn astar<N, Ty: EdgeType>(
+    graph: &StableGraph<N, f64, Ty>,
+    source: NodeIndex,
+    target: NodeIndex,
+    costs: &NodeCosts,
+) -> Option<(f64, Vec<Step>)> {
+    if !costs.allows(source) || !costs.allows(target) {
+        return None;
+    }
+    let estimate = |node| NodeCosts::value(&costs.heuristic, node);
+    let mut distance = HashMap::from([(source, 0.0)]);
+    let mut via: HashMap<NodeIndex, (NodeIndex, EdgeIndex)> = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((OrderedFloat(estimate(source)), source))]);
+    while let Some(Reverse((OrderedFloat(priority), node))) = heap.pop() {
+        let reached = distance[&node];
+        if priority > reached + estimate(node) {
+            continue;
+        }
+        if node == target {
+            break;
+        }
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if !costs.allows(next) {
+                continue;
+            }
+            let candidate = reached + edge.weight() + NodeCosts::value(&costs.weights, next);
+            if distance.get(&next).is_none_or(|&known| candidate < known) {
+                distance.insert(next, candidate);
+                via.insert(next, (node, edge.id()));
+                heap.push(Reverse((OrderedFloat(candidate + estimate(next)), next)));
+            }
+        }
+    }
+
+    let length = *distance.get(&target)?;
+    let mut steps = Vec::new();
+    let mut node = target;
+    while node != source {
+        let (previous, edge) = via[&node];
+        steps.push((previous, node, edge));
+        node = previous;
+    }
+    steps.reverse();
+    Some((length, steps))
+}
+
+/// the shortest path from source to target, bidirectional dijkstra unless node costs
+/// call for a*
+pub fn shortest_path<N, Ty: EdgeType>(
+    graph: &StableGraph<N, f64, Ty>,
+    source: NodeIndex,
+    target: NodeIndex,
+    costs: &NodeCosts,
+) -> Result<Option<(f64, Vec<Step>)>, GraphError> {
+    check_non_negative(graph)?;
+    if costs.is_empty() {
+        return Ok(bidirectional_dijkstra(graph, source, target));
+    }
+    costs.check()?;
+    Ok(astar(graph, source, target, costs))
+}
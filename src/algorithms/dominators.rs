@@ -0,0 +1,68 @@
+use petgraph::algo::dominators::{simple_fast, Dominators};
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::{Directed, Direction};
+use std::collections::HashMap;
+
+/// pairs every node reachable from the root with its immediate dominator,
+/// the root itself is reported with no dominator
+pub fn immediate_dominators<N, E>(
+    graph: &StableGraph<N, E, Directed>,
+    root: NodeIndex,
+) -> Vec<(NodeIndex, Option<NodeIndex>)> {
+    let dominators = simple_fast(graph, root);
+    graph
+        .node_indices()
+        .filter(|&n| dominators.dominators(n).is_some())
+        .map(|n| (n, dominators.immediate_dominator(n)))
+        .collect()
+}
+
+/// computes the dominance frontier of every reachable node as (node, frontier node)
+/// pairs, walking up the dominator tree from each predecessor of a join point
+pub fn dominance_frontiers<N, E>(
+    graph: &StableGraph<N, E, Directed>,
+    root: NodeIndex,
+) -> Vec<(NodeIndex, NodeIndex)> {
+    let dominators: Dominators<NodeIndex> = simple_fast(graph, root);
+    let reachable = |n: NodeIndex| dominators.dominators(n).is_some();
+    let mut frontiers: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+
+    for join in graph.node_indices().filter(|&n| reachable(n)) {
+        let mut predecessors: Vec<NodeIndex> = graph
+            .neighbors_directed(join, Direction::Incoming)
+            .filter(|&p| reachable(p))
+            .collect();
+        predecessors.sort();
+        predecessors.dedup();
+        // the root has an implicit entry edge, so a single back edge already makes it a join
+        let joins = if join == root { 1 } else { 2 };
+        if predecessors.len() < joins {
+            continue;
+        }
+        let idom = dominators.immediate_dominator(join);
+        for predecessor in predecessors {
+            let mut runner = Some(predecessor);
+            while let Some(node) = runner {
+                if Some(node) == idom {
+                    break;
+                }
+                let frontier = frontiers.entry(node).or_default();
+                if !frontier.contains(&join) {
+                    frontier.push(join);
+                }
+                runner = dominators.immediate_dominator(node);
+            }
+        }
+    }
+
+    graph
+        .node_indices()
+        .flat_map(|n| {
+            frontiers
+                .remove(&n)
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |f| (n, f))
+        })
+        .collect()
+}
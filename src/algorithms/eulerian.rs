@@ -0,0 +1,142 @@
+use crate::graph::GraphError;
+use petgraph::stable_graph::{EdgeIndex, NodeIndex, StableGraph};
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+use petgraph::EdgeType;
+
+/// result of checking the degree and connectivity conditions of a graph
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EulerianKind {
+    Circuit,
+    Path { start: NodeIndex, end: NodeIndex },
+    NotEulerian,
+}
+
+/// classifies the graph by the kind of eulerian walk it admits
+pub fn eulerian_kind<N, E, Ty: EdgeType>(graph: &StableGraph<N, E, Ty>) -> EulerianKind {
+    let bound = graph.node_bound();
+    let mut out_degree = vec![0i64; bound];
+    let mut in_degree = vec![0i64; bound];
+    let mut components = UnionFind::<usize>::new(bound);
+
+    for edge in graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        out_degree[a] += 1;
+        in_degree[b] += 1;
+        components.union(a, b);
+    }
+
+    // every node carrying an edge has to sit in the same weakly connected component
+    let mut touched = graph
+        .node_indices()
+        .map(|n| n.index())
+        .filter(|&n| out_degree[n] + in_degree[n] > 0);
+    if let Some(first) = touched.next() {
+        let root = components.find(first);
+        if touched.any(|n| components.find(n) != root) {
+            return EulerianKind::NotEulerian;
+        }
+    }
+
+    if graph.is_directed() {
+        let mut start = None;
+        let mut end = None;
+        for node in graph.node_indices() {
+            match out_degree[node.index()] - in_degree[node.index()] {
+                0 => {}
+                1 if start.is_none() => start = Some(node),
+                -1 if end.is_none() => end = Some(node),
+                _ => return EulerianKind::NotEulerian,
+            }
+        }
+        match (start, end) {
+            (None, None) => EulerianKind::Circuit,
+            (Some(start), Some(end)) => EulerianKind::Path { start, end },
+            _ => EulerianKind::NotEulerian,
+        }
+    } else {
+        // a self loop adds two to the degree of its node so it never makes it odd
+        let odd: Vec<NodeIndex> = graph
+            .node_indices()
+            .filter(|n| (out_degree[n.index()] + in_degree[n.index()]) % 2 == 1)
+            .collect();
+        match odd.as_slice() {
+            [] => EulerianKind::Circuit,
+            [start, end] => EulerianKind::Path {
+                start: *start,
+                end: *end,
+            },
+            _ => EulerianKind::NotEulerian,
+        }
+    }
+}
+
+/// builds an eulerian path with hierholzer's algorithm, returning the traversed
+/// (from, to, edge) triples in walk order so undirected edges keep their orientation
+pub fn eulerian_path<N, E, Ty: EdgeType>(
+    graph: &StableGraph<N, E, Ty>,
+    start: Option<NodeIndex>,
+) -> Result<Vec<(NodeIndex, NodeIndex, EdgeIndex)>, GraphError> {
+    if graph.edge_count() == 0 {
+        return Ok(Vec::new());
+    }
+
+    let has_edges = |n: NodeIndex| graph.edges(n).next().is_some();
+    let start = match (eulerian_kind(graph), start) {
+        (EulerianKind::NotEulerian, _) => {
+            return Err(GraphError::NoSolution("graph has no Eulerian path".into()))
+        }
+        (EulerianKind::Circuit, Some(node)) if !has_edges(node) => {
+            return Err(GraphError::NoSolution(
+                "start node has no incident edges".into(),
+            ))
+        }
+        (EulerianKind::Circuit, Some(node)) => node,
+        (EulerianKind::Circuit, None) => graph
+            .node_indices()
+            .find(|&n| has_edges(n))
+            .expect("graph has at least one edge"),
+        (EulerianKind::Path { start, .. }, None) => start,
+        (EulerianKind::Path { start, end }, Some(node)) => {
+            if node == start || (node == end && !graph.is_directed()) {
+                node
+            } else {
+                return Err(GraphError::NoSolution(
+                    "an Eulerian path cannot begin at the given start node".into(),
+                ));
+            }
+        }
+    };
+
+    let mut adjacency: Vec<Vec<(EdgeIndex, NodeIndex)>> = vec![Vec::new(); graph.node_bound()];
+    for edge in graph.edge_references() {
+        adjacency[edge.source().index()].push((edge.id(), edge.target()));
+        if !graph.is_directed() && edge.source() != edge.target() {
+            adjacency[edge.target().index()].push((edge.id(), edge.source()));
+        }
+    }
+
+    let mut used = vec![false; graph.edge_bound()];
+    let mut cursor = vec![0usize; graph.node_bound()];
+    let mut stack: Vec<(NodeIndex, Option<(NodeIndex, EdgeIndex)>)> = vec![(start, None)];
+    let mut walk = Vec::with_capacity(graph.edge_count());
+
+    while let Some(&(node, via)) = stack.last() {
+        let neighbours = &adjacency[node.index()];
+        let position = &mut cursor[node.index()];
+        while *position < neighbours.len() && used[neighbours[*position].0.index()] {
+            *position += 1;
+        }
+        if let Some(&(edge, next)) = neighbours.get(*position) {
+            used[edge.index()] = true;
+            stack.push((next, Some((node, edge))));
+        } else {
+            stack.pop();
+            if let Some((previous, edge)) = via {
+                walk.push((previous, node, edge));
+            }
+        }
+    }
+    walk.reverse();
+    Ok(walk)
+}
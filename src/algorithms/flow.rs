@@ -0,0 +1,185 @@
+use crate::graph::GraphError;
+use crate::pg_utils::is_numerically_zero;
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// a directed arc offered to the flow solver, with nodes given as dense indices
+#[derive(Clone, Copy, Debug)]
+pub struct FlowArc {
+    pub from: usize,
+    pub to: usize,
+    pub capacity: f64,
+    pub cost: f64,
+}
+
+/// residual network where arc 2i is the forward copy of input arc i and 2i + 1 its reverse
+struct Residual {
+    head: Vec<usize>,
+    capacity: Vec<f64>,
+    cost: Vec<f64>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl Residual {
+    fn new(nodes: usize) -> Self {
+        Residual {
+            head: Vec::new(),
+            capacity: Vec::new(),
+            cost: Vec::new(),
+            outgoing: vec![Vec::new(); nodes],
+        }
+    }
+
+    fn add(&mut self, from: usize, to: usize, capacity: f64, cost: f64) {
+        for (tail, head, capacity, cost) in [(from, to, capacity, cost), (to, from, 0.0, -cost)] {
+            self.outgoing[tail].push(self.head.len());
+            self.head.push(head);
+            self.capacity.push(capacity);
+            self.cost.push(cost);
+        }
+    }
+
+    fn open(&self, arc: usize) -> bool {
+        self.capacity[arc] > 0.0 && !is_numerically_zero(self.capacity[arc])
+    }
+}
+
+/// minimum cost flow by successive shortest paths: node potentials start from a
+/// bellman-ford pass (so negative costs are allowed) and every augmentation follows a
+/// dijkstra path over reduced costs. `supply` is positive at sources and negative at
+/// sinks, and the returned flows are indexed like `arcs`
+pub fn min_cost_flow(
+    nodes: usize,
+    arcs: &[FlowArc],
+    supply: &[f64],
+) -> Result<Vec<f64>, GraphError> {
+    let total: f64 = supply.iter().filter(|s| **s > 0.0).sum();
+    if !is_numerically_zero(supply.iter().sum::<f64>()) {
+        return Err(GraphError::InvalidArgument(
+            "node supplies and demands must sum to zero".into(),
+        ));
+    }
+    if let Some(arc) = arcs
+        .iter()
+        .find(|a| a.capacity < 0.0 || a.capacity.is_nan() || a.cost.is_nan())
+    {
+        return Err(GraphError::InvalidArgument(format!(
+            "invalid capacity {} or cost {}",
+            arc.capacity, arc.cost
+        )));
+    }
+
+    let (source, sink) = (nodes, nodes + 1);
+    let mut residual = Residual::new(nodes + 2);
+    for arc in arcs {
+        residual.add(arc.from, arc.to, arc.capacity, arc.cost);
+    }
+    for (node, &amount) in supply.iter().enumerate() {
+        if amount > 0.0 {
+            residual.add(source, node, amount, 0.0);
+        } else if amount < 0.0 {
+            residual.add(node, sink, -amount, 0.0);
+        }
+    }
+
+    let mut potential = initial_potentials(&residual, source)?;
+    let mut sent = 0.0;
+    while !is_numerically_zero(total - sent) {
+        let (distance, via) = reduced_cost_paths(&residual, &potential, source);
+        if distance[sink].is_infinite() {
+            return Err(GraphError::NoSolution(
+                "supplies cannot be routed to the demands within the capacities".into(),
+            ));
+        }
+        for (p, d) in potential.iter_mut().zip(&distance) {
+            if d.is_finite() {
+                *p += d;
+            }
+        }
+
+        let mut amount = total - sent;
+        let mut node = sink;
+        while let Some(arc) = via[node] {
+            amount = amount.min(residual.capacity[arc]);
+            node = residual.head[arc ^ 1];
+        }
+        let mut node = sink;
+        while let Some(arc) = via[node] {
+            residual.capacity[arc] -= amount;
+            residual.capacity[arc ^ 1] += amount;
+            node = residual.head[arc ^ 1];
+        }
+        sent += amount;
+    }
+
+    Ok((0..arcs.len())
+        .map(|i| residual.capacity[2 * i + 1])
+        .collect())
+}
+
+/// shortest distances from the super source over open arcs, rejecting negative cycles
+fn initial_potentials(residual: &Residual, source: usize) -> Result<Vec<f64>, GraphError> {
+    let nodes = residual.outgoing.len();
+    let mut distance = vec![f64::INFINITY; nodes];
+    distance[source] = 0.0;
+    for round in 0..nodes {
+        let mut changed = false;
+        for tail in 0..nodes {
+            if distance[tail].is_infinite() {
+                continue;
+            }
+            for &arc in &residual.outgoing[tail] {
+                let candidate = distance[tail] + residual.cost[arc];
+                if residual.open(arc) && candidate < distance[residual.head[arc]] {
+                    distance[residual.head[arc]] = candidate;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+        if round + 1 == nodes {
+            return Err(GraphError::NoSolution(
+                "arc costs contain a negative cycle".into(),
+            ));
+        }
+    }
+    Ok(distance
+        .into_iter()
+        .map(|d| if d.is_finite() { d } else { 0.0 })
+        .collect())
+}
+
+/// dijkstra over reduced costs, returning distances and the arc used to reach each node
+fn reduced_cost_paths(
+    residual: &Residual,
+    potential: &[f64],
+    source: usize,
+) -> (Vec<f64>, Vec<Option<usize>>) {
+    let nodes = residual.outgoing.len();
+    let mut distance = vec![f64::INFINITY; nodes];
+    let mut via = vec![None; nodes];
+    distance[source] = 0.0;
+    let mut heap = BinaryHeap::from([Reverse((OrderedFloat(0.0), source))]);
+    while let Some(Reverse((OrderedFloat(reached), tail))) = heap.pop() {
+        if reached > distance[tail] {
+            continue;
+        }
+        for &arc in &residual.outgoing[tail] {
+            if !residual.open(arc) {
+                continue;
+            }
+            let head = residual.head[arc];
+            // reduced costs are non-negative up to rounding, clamp so dijkstra stays valid
+            let reduced = (residual.cost[arc] + potential[tail] - potential[head]).max(0.0);
+            if reached + reduced < distance[head] {
+                distance[head] = reached + reduced;
+                via[head] = Some(arc);
+                heap.push(Reverse((OrderedFloat(distance[head]), head)));
+            }
+        }
+    }
+    (distance, via)
+}
@@ -0,0 +1,14 @@
+pub mod ancestry;
+pub mod approximation;
+pub mod cliques;
+pub mod cut;
+pub mod cycles;
+pub mod distance;
+pub mod dominators;
+pub mod eulerian;
+pub mod flow;
+pub mod reachability;
+pub mod similarity;
+pub mod traversal;
+pub mod trees;
+pub mod walks;
@@ -0,0 +1,121 @@
+use petgraph::algo::tarjan_scc;
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences, NodeIndexable};
+use petgraph::EdgeType;
+use std::collections::HashSet;
+
+/// reachability index over the condensation of the graph, each strongly connected
+/// component carries a [low, rank] post-order interval and a target whose interval
+/// is not nested inside the source's interval can never be reached from it
+pub struct ReachabilityIndex {
+    component: Vec<Option<usize>>,
+    successors: Vec<Vec<usize>>,
+    low: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl ReachabilityIndex {
+    pub fn new<N, E, Ty: EdgeType>(graph: &StableGraph<N, E, Ty>) -> Self {
+        let mut component = vec![None; graph.node_bound()];
+        let components = tarjan_scc(graph);
+        for (id, members) in components.iter().enumerate() {
+            for node in members {
+                component[node.index()] = Some(id);
+            }
+        }
+
+        let mut successors = vec![Vec::new(); components.len()];
+        if graph.is_directed() {
+            for edge in graph.edge_references() {
+                let a = component[edge.source().index()].expect("edge endpoints exist");
+                let b = component[edge.target().index()].expect("edge endpoints exist");
+                if a != b {
+                    successors[a].push(b);
+                }
+            }
+            for next in successors.iter_mut() {
+                next.sort_unstable();
+                next.dedup();
+            }
+        }
+
+        let (low, rank) = post_order_intervals(&successors);
+        ReachabilityIndex {
+            component,
+            successors,
+            low,
+            rank,
+        }
+    }
+
+    fn contains(&self, outer: usize, inner: usize) -> bool {
+        self.low[outer] <= self.low[inner] && self.rank[inner] <= self.rank[outer]
+    }
+
+    /// true when a directed path leads from `source` to `target`, a node always reaches itself
+    pub fn reaches(&self, source: NodeIndex, target: NodeIndex) -> bool {
+        let (Some(Some(from)), Some(Some(to))) = (
+            self.component.get(source.index()),
+            self.component.get(target.index()),
+        ) else {
+            return false;
+        };
+        let (from, to) = (*from, *to);
+        if from == to {
+            return true;
+        }
+        if !self.contains(from, to) {
+            return false;
+        }
+        // the interval test only rules pairs out, so confirm with a search pruned by it
+        let mut seen = HashSet::from([from]);
+        let mut stack = vec![from];
+        while let Some(current) = stack.pop() {
+            for &next in &self.successors[current] {
+                if next == to {
+                    return true;
+                }
+                if self.contains(next, to) && seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        false
+    }
+}
+
+/// post-order rank of every dag node and the smallest rank among its descendants
+fn post_order_intervals(successors: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let count = successors.len();
+    let mut low = vec![usize::MAX; count];
+    let mut rank = vec![usize::MAX; count];
+    let mut visited = vec![false; count];
+    let mut next_rank = 0;
+
+    for root in 0..count {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut stack = vec![(root, 0usize)];
+        while let Some((node, child)) = stack.last_mut() {
+            let node = *node;
+            if let Some(&next) = successors[node].get(*child) {
+                *child += 1;
+                if !visited[next] {
+                    visited[next] = true;
+                    stack.push((next, 0));
+                }
+            } else {
+                stack.pop();
+                rank[node] = next_rank;
+                next_rank += 1;
+                low[node] = successors[node]
+                    .iter()
+                    .map(|&s| low[s])
+                    .fold(rank[node], usize::min);
+            }
+        }
+    }
+    (low, rank)
+}
@@ -0,0 +1,71 @@
+use super::cliques::{neighbour_sets, NodeSet};
+use crate::pg_utils::safe_divide;
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::Undirected;
+use std::collections::HashMap;
+
+/// neighbourhood overlap scores for one candidate pair
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PairScores {
+    pub common_neighbors: u32,
+    pub jaccard: f64,
+    pub adamic_adar: f64,
+    pub resource_allocation: f64,
+    pub preferential_attachment: f64,
+}
+
+/// scores candidate pairs against neighbour sets built once for the whole graph
+pub struct LinkPredictor {
+    neighbours: HashMap<NodeIndex, NodeSet>,
+}
+
+impl LinkPredictor {
+    pub fn new<N, E>(graph: &StableGraph<N, E, Undirected>) -> Self {
+        LinkPredictor {
+            neighbours: neighbour_sets(graph),
+        }
+    }
+
+    fn degree(&self, node: NodeIndex) -> f64 {
+        self.neighbours[&node].len() as f64
+    }
+
+    pub fn score(&self, a: NodeIndex, b: NodeIndex) -> PairScores {
+        let (na, nb) = (&self.neighbours[&a], &self.neighbours[&b]);
+        let common: Vec<NodeIndex> = na.intersection(nb).copied().collect();
+        let union = na.union(nb).count() as f64;
+        PairScores {
+            common_neighbors: common.len() as u32,
+            jaccard: safe_divide(common.len() as f64, union),
+            adamic_adar: common
+                .iter()
+                .map(|&w| safe_divide(1.0, self.degree(w).ln()))
+                .sum(),
+            resource_allocation: common
+                .iter()
+                .map(|&w| safe_divide(1.0, self.degree(w)))
+                .sum(),
+            preferential_attachment: self.degree(a) * self.degree(b),
+        }
+    }
+
+    /// every unordered pair of distinct, non adjacent nodes sharing at least one neighbour
+    pub fn two_hop_pairs(&self) -> Vec<(NodeIndex, NodeIndex)> {
+        let mut nodes: Vec<NodeIndex> = self.neighbours.keys().copied().collect();
+        nodes.sort();
+        let mut pairs = Vec::new();
+        for &a in &nodes {
+            let mut candidates = NodeSet::new();
+            for w in &self.neighbours[&a] {
+                candidates.extend(self.neighbours[w].iter().filter(|&&b| b > a));
+            }
+            pairs.extend(
+                candidates
+                    .into_iter()
+                    .filter(|b| !self.neighbours[&a].contains(b))
+                    .map(|b| (a, b)),
+            );
+        }
+        pairs
+    }
+}
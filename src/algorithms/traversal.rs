@@ -0,0 +1,111 @@
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::visit::NodeIndexable;
+use petgraph::EdgeType;
+use std::collections::VecDeque;
+
+/// a node reached by a traversal together with the tree edge that reached it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Visit {
+    pub node: NodeIndex,
+    pub depth: u32,
+    pub parent: Option<NodeIndex>,
+}
+
+/// breadth first search from a single source, in visit order
+pub fn bfs<N, E, Ty: EdgeType>(
+    graph: &StableGraph<N, E, Ty>,
+    source: NodeIndex,
+    max_depth: Option<u32>,
+) -> Vec<Visit> {
+    multi_source_bfs(graph, &[source], max_depth)
+        .into_iter()
+        .map(|(visit, _)| visit)
+        .collect()
+}
+
+/// breadth first search seeded with every source at depth zero, so each node is
+/// labelled with the source nearest to it in hops (ties go to the earlier source)
+pub fn multi_source_bfs<N, E, Ty: EdgeType>(
+    graph: &StableGraph<N, E, Ty>,
+    sources: &[NodeIndex],
+    max_depth: Option<u32>,
+) -> Vec<(Visit, NodeIndex)> {
+    let mut seen = vec![false; graph.node_bound()];
+    let mut queue = VecDeque::new();
+    let mut visits = Vec::new();
+    for &source in sources {
+        if !std::mem::replace(&mut seen[source.index()], true) {
+            queue.push_back((
+                Visit {
+                    node: source,
+                    depth: 0,
+                    parent: None,
+                },
+                source,
+            ));
+        }
+    }
+    while let Some((visit, origin)) = queue.pop_front() {
+        visits.push((visit, origin));
+        if max_depth.is_some_and(|max| visit.depth >= max) {
+            continue;
+        }
+        for next in graph.neighbors(visit.node) {
+            if !std::mem::replace(&mut seen[next.index()], true) {
+                queue.push_back((
+                    Visit {
+                        node: next,
+                        depth: visit.depth + 1,
+                        parent: Some(visit.node),
+                    },
+                    origin,
+                ));
+            }
+        }
+    }
+    visits
+}
+
+/// depth first search from a source, in pre-order or post-order
+pub fn dfs<N, E, Ty: EdgeType>(
+    graph: &StableGraph<N, E, Ty>,
+    source: NodeIndex,
+    postorder: bool,
+) -> Vec<Visit> {
+    let mut seen = vec![false; graph.node_bound()];
+    let mut depth = vec![0u32; graph.node_bound()];
+    let mut parent = vec![None; graph.node_bound()];
+    let mut preorder = Vec::new();
+    let mut finished = Vec::new();
+
+    seen[source.index()] = true;
+    let mut stack = vec![(source, graph.neighbors(source).detach())];
+    preorder.push(source);
+    while let Some((node, neighbours)) = stack.last_mut() {
+        let node = *node;
+        match neighbours.next_node(graph) {
+            Some(next) if !seen[next.index()] => {
+                seen[next.index()] = true;
+                depth[next.index()] = depth[node.index()] + 1;
+                parent[next.index()] = Some(node);
+                preorder.push(next);
+                stack.push((next, graph.neighbors(next).detach()));
+            }
+            Some(_) => {}
+            None => {
+                stack.pop();
+                finished.push(node);
+            }
+        }
+    }
+
+    let order = if postorder { finished } else { preorder };
+    order
+        .into_iter()
+        .map(|node| Visit {
+            node,
+            depth: depth[node.index()],
+            parent: parent[node.index()],
+        })
+        .collect()
+}
@@ -0,0 +1,76 @@
+use crate::graph::GraphError;
+use petgraph::algo::is_cyclic_directed;
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::visit::NodeIndexable;
+use petgraph::{Directed, Direction};
+use std::collections::VecDeque;
+
+fn degree<N, E>(graph: &StableGraph<N, E, Directed>, node: NodeIndex, dir: Direction) -> usize {
+    graph.edges_directed(node, dir).count()
+}
+
+/// acyclic with every node having at most one parent
+pub fn is_forest<N, E>(graph: &StableGraph<N, E, Directed>) -> bool {
+    graph
+        .node_indices()
+        .all(|n| degree(graph, n, Direction::Incoming) <= 1)
+        && !is_cyclic_directed(graph)
+}
+
+/// a non-empty forest with a single root
+pub fn is_tree<N, E>(graph: &StableGraph<N, E, Directed>) -> bool {
+    is_forest(graph) && roots(graph).len() == 1
+}
+
+/// nodes without parents
+pub fn roots<N, E>(graph: &StableGraph<N, E, Directed>) -> Vec<NodeIndex> {
+    graph
+        .node_indices()
+        .filter(|&n| degree(graph, n, Direction::Incoming) == 0)
+        .collect()
+}
+
+/// nodes without children
+pub fn leaves<N, E>(graph: &StableGraph<N, E, Directed>) -> Vec<NodeIndex> {
+    graph
+        .node_indices()
+        .filter(|&n| degree(graph, n, Direction::Outgoing) == 0)
+        .collect()
+}
+
+/// depth of every node below its root, breadth first from each root in turn
+pub fn depths<N, E>(
+    graph: &StableGraph<N, E, Directed>,
+) -> Result<Vec<(NodeIndex, u32)>, GraphError> {
+    if !is_forest(graph) {
+        return Err(GraphError::InvalidStructure(
+            "graph is not a forest, a node has several parents or lies on a cycle".into(),
+        ));
+    }
+    let mut reached = Vec::with_capacity(graph.node_count());
+    let mut queue: VecDeque<(NodeIndex, u32)> =
+        roots(graph).into_iter().map(|root| (root, 0)).collect();
+    while let Some((node, depth)) = queue.pop_front() {
+        reached.push((node, depth));
+        queue.extend(graph.neighbors(node).map(|child| (child, depth + 1)));
+    }
+    Ok(reached)
+}
+
+/// number of nodes in the subtree below every node, the node itself included
+pub fn subtree_sizes<N, E>(
+    graph: &StableGraph<N, E, Directed>,
+) -> Result<Vec<(NodeIndex, u32)>, GraphError> {
+    let order = depths(graph)?;
+    let mut size = vec![1u32; graph.node_bound()];
+    // children come after their parent in breadth first order, so fold back to front
+    for &(node, _) in order.iter().rev() {
+        if let Some(parent) = graph.neighbors_directed(node, Direction::Incoming).next() {
+            size[parent.index()] += size[node.index()];
+        }
+    }
+    Ok(order
+        .into_iter()
+        .map(|(node, _)| (node, size[node.index()]))
+        .collect())
+}
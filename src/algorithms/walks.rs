@@ -0,0 +1,79 @@
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::visit::EdgeRef;
+use petgraph::EdgeType;
+use rand::Rng;
+
+/// node2vec walk settings, `p` penalises returning to the previous node and `q`
+/// penalises moving away from it, with p = q = 1 giving a plain weighted walk
+#[derive(Clone, Copy, Debug)]
+pub struct WalkParams {
+    pub walk_length: usize,
+    pub walks_per_node: usize,
+    pub p: f64,
+    pub q: f64,
+}
+
+/// picks an index with probability proportional to its weight, or none when every
+/// weight is zero
+fn sample_weighted<R: Rng>(weights: &[f64], rng: &mut R) -> Option<usize> {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut target = rng.random::<f64>() * total;
+    for (i, &w) in weights.iter().enumerate() {
+        if target < w {
+            return Some(i);
+        }
+        target -= w;
+    }
+    weights.iter().rposition(|&w| w > 0.0)
+}
+
+fn walk_from<N, Ty: EdgeType, R: Rng>(
+    graph: &StableGraph<N, f64, Ty>,
+    start: NodeIndex,
+    params: &WalkParams,
+    rng: &mut R,
+) -> Vec<NodeIndex> {
+    let mut walk = vec![start];
+    let mut candidates = Vec::new();
+    let mut weights = Vec::new();
+    while walk.len() < params.walk_length {
+        let current = walk[walk.len() - 1];
+        let previous = walk.len().checked_sub(2).map(|i| walk[i]);
+        candidates.clear();
+        weights.clear();
+        for edge in graph.edges(current) {
+            let next = edge.target();
+            let bias = match previous {
+                None => 1.0,
+                Some(previous) if next == previous => 1.0 / params.p,
+                Some(previous) if graph.contains_edge(previous, next) => 1.0,
+                Some(_) => 1.0 / params.q,
+            };
+            candidates.push(next);
+            weights.push(edge.weight() * bias);
+        }
+        match sample_weighted(&weights, rng) {
+            Some(i) => walk.push(candidates[i]),
+            None => break,
+        }
+    }
+    walk
+}
+
+/// generates `walks_per_node` biased walks from every start node in turn, a walk
+/// ends early when it reaches a node without outgoing weight
+pub fn random_walks<N, Ty: EdgeType, R: Rng>(
+    graph: &StableGraph<N, f64, Ty>,
+    starts: &[NodeIndex],
+    params: &WalkParams,
+    rng: &mut R,
+) -> Vec<Vec<NodeIndex>> {
+    starts
+        .iter()
+        .flat_map(|&start| std::iter::repeat_n(start, params.walks_per_node))
+        .map(|start| walk_from(graph, start, params, rng))
+        .collect()
+}
@@ -0,0 +1,304 @@
+use crate::graph::{float_values, GraphError};
+use crate::ingest::{MergedRow, MultiEdges};
+use petgraph::stable_graph::EdgeIndex;
+use pyo3_polars::export::polars_core::prelude::*;
+
+/// attribute rows addressed by graph index: index i is described by row `rows[i]` of
+/// `frame`, indices without a row have null attributes
+#[derive(Clone, Debug, Default)]
+pub struct AttributeRows {
+    frame: DataFrame,
+    rows: Vec<Option<IdxSize>>,
+    /// how many rows of `frame` some index points at, the others are dead
+    live: usize,
+}
+
+/// the rows of a delta frame checked against the held columns, see [`AttributeRows::stage`]
+#[derive(Debug)]
+pub struct StagedRows {
+    /// the held frame with the delta's rows appended
+    frame: DataFrame,
+    /// the row the delta's first row lands on
+    start: IdxSize,
+}
+
+impl StagedRows {
+    /// the staged frame with rows folded as [`fold_rows`] does, `merged` naming rows of
+    /// the staged frame
+    pub fn fold(self, policy: MultiEdges, merged: &[MergedRow]) -> Result<Self, GraphError> {
+        if merged.is_empty() {
+            return Ok(self);
+        }
+        Ok(StagedRows {
+            frame: fold_rows(&self.frame, policy, merged)?,
+            start: self.start,
+        })
+    }
+
+    /// the row a row of the delta frame lands on
+    pub fn row(&self, row: IdxSize) -> IdxSize {
+        self.start + row
+    }
+}
+
+/// `frame` with every merged row folded into the row its edge keeps: numeric columns are
+/// combined as `policy` combines weights, a null giving way to the other value, and other
+/// columns keep the first row's value. means of integer columns are stored as floats
+pub fn fold_rows(
+    frame: &DataFrame,
+    policy: MultiEdges,
+    merged: &[MergedRow],
+) -> Result<DataFrame, GraphError> {
+    if merged.is_empty() && policy != MultiEdges::Mean {
+        return Ok(frame.clone());
+    }
+    let columns = frame
+        .get_columns()
+        .iter()
+        .map(|column| {
+            if !column.dtype().is_primitive_numeric() {
+                return Ok(column.clone());
+            }
+            let dtype = match policy {
+                MultiEdges::Mean => DataType::Float64,
+                _ => column.dtype().clone(),
+            };
+            let mut values: Vec<Option<f64>> =
+                column.cast(&DataType::Float64)?.f64()?.iter().collect();
+            for merge in merged {
+                let (kept, row) = (merge.kept as usize, merge.row as usize);
+                values[kept] = match (values[kept], values[row]) {
+                    (Some(value), Some(other)) => {
+                        Some(policy.combine(value, merge.kept_count, other, merge.count))
+                    }
+                    (value, other) => value.or(other),
+                };
+            }
+            Ok(Column::new(column.name().clone(), values).cast(&dtype)?)
+        })
+        .collect::<Result<Vec<Column>, GraphError>>()?;
+    Ok(DataFrame::new(columns)?)
+}
+
+impl AttributeRows {
+    fn row_indices(&self, indices: &[usize]) -> IdxCa {
+        IdxCa::from_iter_options(
+            "row".into(),
+            indices.iter().map(|&i| self.rows.get(i).copied().flatten()),
+        )
+    }
+
+    pub fn has_column(&self, column: &str) -> bool {
+        self.frame.column(column).is_ok()
+    }
+
+    /// rows held, including dead ones no index points at anymore
+    pub fn height(&self) -> usize {
+        self.frame.height()
+    }
+
+    /// attribute rows of the given indices
+    pub fn take(&self, indices: &[usize]) -> Result<DataFrame, GraphError> {
+        Ok(self.frame.take(&self.row_indices(indices))?)
+    }
+
+    /// one attribute column for the given indices
+    pub fn column(&self, column: &str, indices: &[usize]) -> Result<Series, GraphError> {
+        Ok(self
+            .frame
+            .column(column)?
+            .as_materialized_series()
+            .take(&self.row_indices(indices))?)
+    }
+
+    /// the held rows with `frame` appended, columns missing on either side filled with
+    /// nulls and the delta's columns cast to the held dtypes. nothing is changed until the
+    /// result is passed to [`AttributeRows::append`], so a delta that does not fit leaves
+    /// the rows as they were
+    pub fn stage(&self, frame: &DataFrame) -> Result<StagedRows, GraphError> {
+        let mut grown = self.frame.clone();
+        for column in frame.get_columns() {
+            if !self.has_column(column.name()) {
+                let nulls =
+                    Column::full_null(column.name().clone(), grown.height(), column.dtype());
+                grown.with_column(nulls)?;
+            }
+        }
+        let appended = grown
+            .get_columns()
+            .iter()
+            .map(|column| match frame.column(column.name()) {
+                Ok(values) => values.strict_cast(column.dtype()).map_err(|_| {
+                    GraphError::InvalidDataType(format!(
+                        "column {} of dtype {} does not fit the attribute dtype {}",
+                        column.name(),
+                        values.dtype(),
+                        column.dtype()
+                    ))
+                }),
+                Err(_) => Ok(Column::full_null(
+                    column.name().clone(),
+                    frame.height(),
+                    column.dtype(),
+                )),
+            })
+            .collect::<Result<Vec<Column>, GraphError>>()?;
+        let appended = DataFrame::new_with_height(frame.height(), appended)?;
+        let start = grown.height() as IdxSize;
+        grown.vstack_mut(&appended)?;
+        Ok(StagedRows {
+            frame: grown,
+            start,
+        })
+    }
+
+    /// takes over staged rows and points every (index, row of the delta) pair at its
+    /// appended row. indices not assigned keep their rows, so vacant indices a graph reuses
+    /// must be assigned or cleared
+    pub fn append(
+        &mut self,
+        staged: StagedRows,
+        assigned: impl IntoIterator<Item = (usize, IdxSize)>,
+    ) -> Result<(), GraphError> {
+        self.frame = staged.frame;
+        for (index, row) in assigned {
+            if index >= self.rows.len() {
+                self.rows.resize(index + 1, None);
+            }
+            if self.rows[index].replace(staged.start + row).is_none() {
+                self.live += 1;
+            }
+        }
+        self.compact()
+    }
+
+    /// forgets the row of an index, its attributes read as null afterwards. the row stays
+    /// in the frame until [`AttributeRows::compact`] drops it
+    pub fn clear(&mut self, index: usize) {
+        if let Some(row) = self.rows.get_mut(index) {
+            if row.take().is_some() {
+                self.live -= 1;
+            }
+        }
+    }
+
+    /// once at least half the rows are dead, keeps only the live ones in a single chunk so
+    /// deltas and removals do not grow or fragment the frame without bound
+    pub fn compact(&mut self) -> Result<(), GraphError> {
+        let dead = self.frame.height() - self.live;
+        if dead == 0 || dead * 2 < self.frame.height() {
+            return Ok(());
+        }
+        let indices: Vec<usize> = (0..self.rows.len())
+            .filter(|&i| self.rows[i].is_some())
+            .collect();
+        let mut frame = self.take(&indices)?;
+        frame.rechunk_mut();
+        self.frame = frame;
+        for (row, &index) in indices.iter().enumerate() {
+            self.rows[index] = Some(row as IdxSize);
+        }
+        Ok(())
+    }
+}
+
+/// the input columns that travel with the edges, addressed by edge index
+#[derive(Clone, Debug, Default)]
+pub struct EdgeAttributes {
+    rows: AttributeRows,
+    /// how many input rows each edge merges
+    counts: Vec<u32>,
+    /// the column the stored edge weights were read from
+    weight_column: Option<String>,
+}
+
+impl EdgeAttributes {
+    /// an empty store whose weights come from `weight_column`
+    pub fn new(weight_column: Option<String>) -> Self {
+        EdgeAttributes {
+            weight_column,
+            ..EdgeAttributes::default()
+        }
+    }
+
+    /// the row holding an edge's attributes
+    pub fn row(&self, edge: EdgeIndex) -> Option<IdxSize> {
+        self.rows.rows.get(edge.index()).copied().flatten()
+    }
+
+    /// the number of input rows merged into an edge
+    pub fn count(&self, edge: EdgeIndex) -> u32 {
+        self.counts.get(edge.index()).copied().unwrap_or(1)
+    }
+
+    /// records that `more` input rows were merged into an edge
+    pub fn merge_into(&mut self, edge: EdgeIndex, more: u32) {
+        let count = self.count(edge) + more;
+        self.set_count(edge, count);
+    }
+
+    fn set_count(&mut self, edge: EdgeIndex, count: u32) {
+        if edge.index() >= self.counts.len() {
+            self.counts.resize(edge.index() + 1, 1);
+        }
+        self.counts[edge.index()] = count;
+    }
+
+    pub fn weight_column(&self) -> Option<&str> {
+        self.weight_column.as_deref()
+    }
+
+    /// attribute rows of the given edges, without the column the weights came from
+    pub fn rows_of(&self, edges: &[EdgeIndex]) -> Result<DataFrame, GraphError> {
+        let indices: Vec<usize> = edges.iter().map(|e| e.index()).collect();
+        let frame = self.rows.take(&indices)?;
+        Ok(match &self.weight_column {
+            Some(column) if self.rows.has_column(column) => frame.drop(column)?,
+            _ => frame,
+        })
+    }
+
+    /// numeric values of an attribute column for the given edges cast to f64, nulls are
+    /// rejected with the positions of their edges in `edges`
+    pub fn values(&self, column: &str, edges: &[EdgeIndex]) -> Result<Vec<f64>, GraphError> {
+        let indices: Vec<usize> = edges.iter().map(|e| e.index()).collect();
+        let values = self.rows.column(column, &indices)?;
+        float_values(&values, column)
+    }
+
+    /// checks the rows of a delta frame, see [`AttributeRows::stage`]
+    pub fn stage(&self, frame: &DataFrame) -> Result<StagedRows, GraphError> {
+        self.rows.stage(frame)
+    }
+
+    /// appends staged rows for new edges given with their row and merged row count, see
+    /// [`AttributeRows::append`]
+    pub fn append(
+        &mut self,
+        staged: StagedRows,
+        assigned: &[(EdgeIndex, IdxSize, u32)],
+    ) -> Result<(), GraphError> {
+        for &(edge, _, count) in assigned {
+            self.set_count(edge, count);
+        }
+        self.rows.append(
+            staged,
+            assigned.iter().map(|&(edge, row, _)| (edge.index(), row)),
+        )
+    }
+
+    /// the attribute rows behind the edges
+    pub fn rows(&self) -> &AttributeRows {
+        &self.rows
+    }
+
+    /// forgets the attribute row of a removed edge, see [`AttributeRows::clear`]
+    pub fn clear(&mut self, edge: EdgeIndex) {
+        self.rows.clear(edge.index());
+    }
+
+    /// see [`AttributeRows::compact`]
+    pub fn compact(&mut self) -> Result<(), GraphError> {
+        self.rows.compact()
+    }
+}
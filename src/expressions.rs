@@ -1,76 +1,15 @@
-use petgraph::graph::NodeIndex;
-use petgraph::{algo::tarjan_scc, Directed, Graph};
+use crate::graph::{node_key_dtype, node_values_series, GraphError, NodeData};
+use crate::graph_core::GraphCore;
+use crate::ingest::{GraphOptions, NullPolicy};
+use petgraph::stable_graph::StableGraph;
+use petgraph::{algo::tarjan_scc, Directed, EdgeType};
 use pyo3_polars::derive::polars_expr;
 use pyo3_polars::export::polars_core::{
     datatypes::DataType, error::PolarsResult, prelude::*, series::Series,
 };
-use std::collections::HashMap;
-use std::hash::Hash;
 
-trait IntoTypedVec {
-    type Output: Clone + Hash + Eq;
-    fn to_typed_vec(&self) -> PolarsResult<Vec<Self::Output>>;
-    fn vec_to_series(vec: Vec<Self::Output>) -> Series;
-}
-
-impl IntoTypedVec for &ChunkedArray<Int64Type> {
-    type Output = i64;
-    fn to_typed_vec(&self) -> PolarsResult<Vec<i64>> {
-        Ok(self.into_iter().flatten().collect())
-    }
-    fn vec_to_series(vec: Vec<i64>) -> Series {
-        Int64Chunked::from_vec("".into(), vec).into_series()
-    }
-}
-
-impl IntoTypedVec for &ChunkedArray<Int32Type> {
-    type Output = i32;
-    fn to_typed_vec(&self) -> PolarsResult<Vec<i32>> {
-        Ok(self.into_iter().flatten().collect())
-    }
-    fn vec_to_series(vec: Vec<i32>) -> Series {
-        Int32Chunked::from_vec("".into(), vec).into_series()
-    }
-}
-
-impl IntoTypedVec for &ChunkedArray<StringType> {
-    type Output = String;
-    fn to_typed_vec(&self) -> PolarsResult<Vec<String>> {
-        Ok(self.into_iter().flatten().map(|s| s.to_string()).collect())
-    }
-    fn vec_to_series(vec: Vec<String>) -> Series {
-        let ca: ChunkedArray<StringType> = vec.into_iter().collect();
-        ca.into_series()
-    }
-}
-
-pub fn build_graph<T>(
-    sources: &[T],
-    destinations: &[T],
-) -> (Graph<T, (), Directed>, HashMap<T, NodeIndex>)
-where
-    T: Clone + Hash + Eq,
-{
-    let mut graph = Graph::new();
-    let mut node_map = HashMap::new();
-    // add all nodes and build a mapping from value to node index
-    for value in sources.iter().chain(destinations.iter()) {
-        if !node_map.contains_key(value) {
-            let graph_value = value.clone();
-            let node_idx = graph.add_node(graph_value.clone());
-            node_map.insert(graph_value, node_idx);
-        }
-    }
-    // add edges using the hash map for lookups
-    for (source, dest) in sources.iter().zip(destinations.iter()) {
-        let source_index = *node_map.get(source).unwrap();
-        let dest_index = *node_map.get(dest).unwrap();
-        graph.add_edge(source_index, dest_index, ());
-    }
-    (graph, node_map)
-}
-
-pub fn get_cycles<T: Clone>(graph: &Graph<T, (), Directed>) -> Vec<Vec<T>> {
+/// the strongly connected components of more than one node
+pub fn get_cycles<Ty: EdgeType>(graph: &StableGraph<NodeData, f64, Ty>) -> Vec<Vec<NodeData>> {
     let sccs = tarjan_scc(graph);
 
     sccs.into_iter()
@@ -84,64 +23,52 @@ pub fn get_cycles<T: Clone>(graph: &Graph<T, (), Directed>) -> Vec<Vec<T>> {
 }
 
 pub fn list_dtype(_input_fields: &[Field]) -> PolarsResult<Field> {
-    let inner_type = match _input_fields[0].dtype() {
-        DataType::Int32 => DataType::Int32,
-        DataType::Int64 => DataType::Int64,
-        DataType::String => DataType::String,
-        dt => {
-            return Err(PolarsError::ComputeError(
-                format!("Unsupported input type: {}", dt).into(),
-            ))
-        }
-    };
+    let inner_type = _input_fields[0].dtype();
+    if node_key_dtype(inner_type).is_err() {
+        return Err(PolarsError::ComputeError(
+            format!("Unsupported input type: {}", inner_type).into(),
+        ));
+    }
     Ok(Field::new(
         "CyclesFound".into(),
-        DataType::List(Box::new(inner_type)),
+        DataType::List(Box::new(inner_type.clone())),
     ))
 }
-fn process_cycles<T, C>(sources: &C, destinations: &C) -> PolarsResult<Vec<Series>>
-where
-    T: Clone + Hash + Eq,
-    C: IntoTypedVec<Output = T>,
-{
-    let sources_vec = sources.to_typed_vec()?;
-    let destinations_vec = destinations.to_typed_vec()?;
-    let (graph, _) = build_graph(&sources_vec, &destinations_vec);
-    let cycles = get_cycles(&graph);
-    Ok(cycles
-        .into_iter()
-        .map(|cycle| <C as IntoTypedVec>::vec_to_series(cycle))
-        .collect())
+
+/// the strongly connected components of more than one node, as lists of the input dtype
+pub fn cycle_lists(sources: &Series, destinations: &Series) -> PolarsResult<Series> {
+    let to_polars = |e: GraphError| PolarsError::ComputeError(e.to_string().into());
+    let frame = DataFrame::new(vec![
+        sources.clone().with_name("source".into()).into(),
+        destinations.clone().with_name("target".into()).into(),
+    ])?;
+    // rows with a null on either side carry no edge
+    let options = GraphOptions {
+        null_policy: NullPolicy::DropRow,
+        ..GraphOptions::default()
+    };
+    let core =
+        GraphCore::<Directed>::build(&frame, "source".into(), "target".into(), None, options)
+            .map_err(to_polars)?;
+    let schema = core.node_schema;
+    let list_values = get_cycles(&core.graph)
+        .iter()
+        .map(|cycle| {
+            let values: Vec<_> = cycle.iter().map(Some).collect();
+            node_values_series("", &schema, &values)
+        })
+        .collect::<Result<Vec<Series>, _>>()
+        .map_err(to_polars)?;
+    if list_values.is_empty() {
+        return Ok(Series::new_empty(
+            "CyclesFound".into(),
+            &DataType::List(Box::new(schema.dtype)),
+        ));
+    }
+    Ok(Series::new("CyclesFound".into(), list_values))
 }
 
 #[polars_expr(output_type_func=list_dtype)]
 pub fn detect_cycle(inputs: &[Series]) -> PolarsResult<Series> {
-    if inputs[0].dtype() != inputs[1].dtype() {
-        return Err(PolarsError::ComputeError(
-            "Input columns must have the same datatype".into(),
-        ));
-    }
-    match inputs[0].dtype() {
-        DataType::Int64 => {
-            let sources = inputs[0].i64()?;
-            let destinations = inputs[1].i64()?;
-            let list_values = process_cycles(&sources, &destinations)?;
-            Ok(Series::new("CyclesFound".into(), list_values))
-        }
-        DataType::Int32 => {
-            let sources = inputs[0].i32()?;
-            let destinations = inputs[1].i32()?;
-            let list_values = process_cycles(&sources, &destinations)?;
-            Ok(Series::new("CyclesFound".into(), list_values))
-        }
-        DataType::String => {
-            let sources = inputs[0].str()?;
-            let destinations = inputs[1].str()?;
-            let list_values = process_cycles(&sources, &destinations)?;
-            Ok(Series::new("CyclesFound".into(), list_values))
-        }
-        dt => Err(PolarsError::ComputeError(
-            format!("Unsupported input type: {}", dt).into(),
-        )),
-    }
+    cycle_lists(&inputs[0], &inputs[1])
 }
@@ -1,12 +1,36 @@
+use crate::algorithms::ancestry::{self, AncestorIndex};
+use crate::algorithms::approximation;
+use crate::algorithms::cliques;
+use crate::algorithms::cut;
+use crate::algorithms::cycles;
+use crate::algorithms::distance::{self, NodeCosts, ShortestPathTree};
+use crate::algorithms::dominators;
+use crate::algorithms::eulerian::{self, EulerianKind};
+use crate::algorithms::flow::{self, FlowArc};
+use crate::algorithms::reachability::ReachabilityIndex;
+use crate::algorithms::similarity::{LinkPredictor, PairScores};
+use crate::algorithms::traversal::{self, Visit};
+use crate::algorithms::trees;
+use crate::algorithms::walks::{self, WalkParams};
+use crate::attributes::{AttributeRows, EdgeAttributes};
+use crate::graph_core::GraphCore;
+use crate::ingest::{self, FrameInput, GraphOptions, KeyColumns, KeySpec, WeightSpec};
+use crate::pg_utils::is_numerically_zero;
 use itertools::izip;
 use ordered_float::OrderedFloat;
+use petgraph::algo::{has_path_connecting, is_cyclic_directed, is_cyclic_undirected};
 use petgraph::graph::NodeIndex;
-use petgraph::stable_graph::StableGraph;
-use petgraph::{Directed, Undirected};
+use petgraph::stable_graph::{EdgeIndex, StableGraph};
+use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+use petgraph::{Directed, Direction, EdgeType, Undirected};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyTuple};
 use pyo3_polars::export::polars_core::{datatypes::DataType, prelude::*, series::Series};
-use pyo3_polars::PyDataFrame;
+use pyo3_polars::{PyDataFrame, PySeries};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::hash::Hash;
 use thiserror::Error;
@@ -19,6 +43,27 @@ pub enum GraphError {
     PolarsError(#[from] PolarsError),
     #[error("Invalid Data Format:{0}")]
     InvalidDataType(String),
+    #[error("Node not found:{0}")]
+    NodeNotFound(String),
+    #[error("No solution:{0}")]
+    NoSolution(String),
+    #[error("Invalid graph structure:{0}")]
+    InvalidStructure(String),
+    #[error("Invalid argument:{0}")]
+    InvalidArgument(String),
+    #[error("Invalid rows:{0} at rows {rows}", rows = row_preview(.1))]
+    InvalidRows(String, Vec<usize>),
+}
+
+/// the first few offending row indices, enough to find them without flooding the message
+fn row_preview(rows: &[usize]) -> String {
+    const SHOWN: usize = 10;
+    let head: Vec<String> = rows.iter().take(SHOWN).map(|r| r.to_string()).collect();
+    if rows.len() > SHOWN {
+        format!("[{}, ...] ({} rows)", head.join(", "), rows.len())
+    } else {
+        format!("[{}]", head.join(", "))
+    }
 }
 impl From<GraphError> for PyErr {
     fn from(err: GraphError) -> PyErr {
@@ -28,175 +73,1800 @@ impl From<GraphError> for PyErr {
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub enum NodeData {
     Int(i64),
+    /// unsigned values beyond `i64::MAX`, smaller ones are always stored as `Int`
+    UInt(u64),
     Float(OrderedFloat<f64>),
     String(String),
+    Binary(Vec<u8>),
+    /// composite key read from several columns
+    Tuple(Vec<NodeData>),
+}
+
+impl<'py> FromPyObject<'py> for NodeData {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(i) = ob.extract::<i64>() {
+            Ok(NodeData::Int(i))
+        } else if let Ok(u) = ob.extract::<u64>() {
+            Ok(NodeData::UInt(u))
+        } else if let Ok(f) = ob.extract::<f64>() {
+            Ok(NodeData::Float(OrderedFloat(f)))
+        } else if let Ok(s) = ob.extract::<String>() {
+            Ok(NodeData::String(s))
+        } else if let Ok(b) = ob.downcast::<PyBytes>() {
+            Ok(NodeData::Binary(b.as_bytes().to_vec()))
+        } else if let Ok(t) = ob.downcast::<PyTuple>() {
+            Ok(NodeData::Tuple(t.extract()?))
+        } else {
+            Err(GraphError::InvalidDataType(format!(
+                "Unsupported node value:{}",
+                ob.get_type().name()?
+            ))
+            .into())
+        }
+    }
+}
+
+/// a node as a python argument names it. temporal values are kept apart from integers
+/// until the graph's key dtype says which unit they are stored in
+#[derive(Clone, Debug, PartialEq)]
+pub enum NodeArg {
+    Value(NodeData),
+    /// days since 1970-01-01
+    Date(i64),
+    /// microseconds since 1970-01-01, aware datetimes are taken in utc
+    Datetime(i64),
+    /// microseconds since midnight
+    Time(i64),
+    /// microseconds
+    Duration(i64),
+    Tuple(Vec<NodeArg>),
+}
+
+impl From<NodeData> for NodeArg {
+    fn from(node: NodeData) -> Self {
+        NodeArg::Value(node)
+    }
+}
+
+/// total microseconds of a python timedelta
+fn timedelta_micros(delta: &Bound<'_, PyAny>) -> PyResult<i64> {
+    let days: i64 = delta.getattr("days")?.extract()?;
+    let seconds: i64 = delta.getattr("seconds")?.extract()?;
+    let micros: i64 = delta.getattr("microseconds")?.extract()?;
+    Ok((days * 86_400 + seconds) * 1_000_000 + micros)
+}
+
+impl<'py> FromPyObject<'py> for NodeArg {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let datetime = ob.py().import("datetime")?;
+        // datetime is a subclass of date, so it is tried first
+        let datetime_type = datetime.getattr("datetime")?;
+        if ob.is_instance(&datetime_type)? {
+            let epoch = if ob.getattr("tzinfo")?.is_none() {
+                datetime_type.call1((1970, 1, 1))?
+            } else {
+                let utc = datetime.getattr("timezone")?.getattr("utc")?;
+                datetime_type.call1((1970, 1, 1, 0, 0, 0, 0, utc))?
+            };
+            Ok(NodeArg::Datetime(timedelta_micros(&ob.sub(epoch)?)?))
+        } else if ob.is_instance(&datetime.getattr("date")?)? {
+            // 719163 is the proleptic ordinal of 1970-01-01
+            let ordinal: i64 = ob.call_method0("toordinal")?.extract()?;
+            Ok(NodeArg::Date(ordinal - 719_163))
+        } else if ob.is_instance(&datetime.getattr("time")?)? {
+            let part = |name: &str| -> PyResult<i64> { ob.getattr(name)?.extract() };
+            let seconds = part("hour")? * 3600 + part("minute")? * 60 + part("second")?;
+            Ok(NodeArg::Time(seconds * 1_000_000 + part("microsecond")?))
+        } else if ob.is_instance(&datetime.getattr("timedelta")?)? {
+            Ok(NodeArg::Duration(timedelta_micros(ob)?))
+        } else if let Ok(t) = ob.downcast::<PyTuple>() {
+            Ok(NodeArg::Tuple(t.extract()?))
+        } else {
+            Ok(NodeArg::Value(ob.extract()?))
+        }
+    }
+}
+
+/// microseconds in a polars time unit, rounded down below milliseconds
+fn micros_in(unit: TimeUnit, micros: i64) -> i64 {
+    match unit {
+        TimeUnit::Nanoseconds => micros * 1_000,
+        TimeUnit::Microseconds => micros,
+        TimeUnit::Milliseconds => micros.div_euclid(1_000),
+    }
+}
+
+/// the node value a python argument names in a graph keyed by `dtype`
+fn coerce_node(dtype: &DataType, node: NodeArg) -> Result<NodeData, GraphError> {
+    Ok(match (dtype, node) {
+        (DataType::Date, NodeArg::Date(days)) => NodeData::Int(days),
+        (DataType::Datetime(unit, _), NodeArg::Datetime(micros)) => {
+            NodeData::Int(micros_in(*unit, micros))
+        }
+        (DataType::Duration(unit), NodeArg::Duration(micros)) => {
+            NodeData::Int(micros_in(*unit, micros))
+        }
+        (DataType::Time, NodeArg::Time(micros)) => NodeData::Int(micros * 1_000),
+        (DataType::Float32 | DataType::Float64, NodeArg::Value(NodeData::Int(i))) => {
+            NodeData::Float(OrderedFloat(i as f64))
+        }
+        (DataType::Float32 | DataType::Float64, NodeArg::Value(NodeData::UInt(u))) => {
+            NodeData::Float(OrderedFloat(u as f64))
+        }
+        (DataType::Struct(fields), NodeArg::Tuple(parts)) if fields.len() == parts.len() => {
+            NodeData::Tuple(
+                fields
+                    .iter()
+                    .zip(parts)
+                    .map(|(field, part)| coerce_node(field.dtype(), part))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        (_, NodeArg::Value(node)) => node,
+        (dtype, node) => {
+            return Err(GraphError::InvalidDataType(format!(
+                "{:?} cannot name a node of dtype {}",
+                node, dtype
+            )))
+        }
+    })
+}
+
+/// the dtype node values are hashed as: integers and temporal values by their integer
+/// representation, categoricals and enums by their labels and structs (composite keys)
+/// field by field
+pub fn node_key_dtype(dtype: &DataType) -> Result<DataType, GraphError> {
+    Ok(match dtype {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::Date
+        | DataType::Datetime(..)
+        | DataType::Duration(..)
+        | DataType::Time => DataType::Int64,
+        DataType::UInt64 => DataType::UInt64,
+        DataType::Float32 | DataType::Float64 => DataType::Float64,
+        DataType::String | DataType::Categorical(..) | DataType::Enum(..) => DataType::String,
+        DataType::Binary => DataType::Binary,
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|f| Ok(Field::new(f.name().clone(), node_key_dtype(f.dtype())?)))
+                .collect::<Result<Vec<Field>, GraphError>>()?,
+        ),
+        dt => {
+            return Err(GraphError::InvalidDataType(format!(
+                "Unsupported dtype:{}",
+                dt
+            )))
+        }
+    })
+}
+
+/// the dtype the graph's nodes were read from, results are cast back to it
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeSchema {
+    pub dtype: DataType,
+}
+
+impl NodeSchema {
+    pub fn new(dtype: &DataType) -> Result<Self, GraphError> {
+        node_key_dtype(dtype)?;
+        Ok(NodeSchema {
+            dtype: dtype.clone(),
+        })
+    }
+
+    /// the node value a python argument names in a graph with this schema
+    pub fn coerce(&self, node: NodeArg) -> Result<NodeData, GraphError> {
+        coerce_node(&self.dtype, node)
+    }
+
+    /// rejects keys that do not read as the same node values as this schema's keys
+    pub fn check(&self, dtype: &DataType, what: &str) -> Result<(), GraphError> {
+        if node_key_dtype(dtype)? != node_key_dtype(&self.dtype)? {
+            return Err(GraphError::InvalidDataType(format!(
+                "{} dtype {} does not match the graph's node dtype {}",
+                what, dtype, self.dtype
+            )));
+        }
+        Ok(())
+    }
+
+    /// expands composite node columns of a result frame back into their key columns, named
+    /// as the keys when there is one node column and prefixed with the column name otherwise
+    pub fn expand(&self, df: DataFrame) -> Result<PyDataFrame, GraphError> {
+        if !matches!(self.dtype, DataType::Struct(_)) {
+            return Ok(PyDataFrame(df));
+        }
+        let composite = df
+            .get_columns()
+            .iter()
+            .filter(|c| c.dtype() == &self.dtype)
+            .count();
+        let mut columns = Vec::with_capacity(df.width());
+        for column in df.get_columns() {
+            if column.dtype() != &self.dtype {
+                columns.push(column.clone());
+                continue;
+            }
+            for field in column.struct_()?.fields_as_series() {
+                let name = if composite == 1 {
+                    field.name().clone()
+                } else {
+                    format!("{}_{}", column.name(), field.name()).into()
+                };
+                columns.push(field.with_name(name).into());
+            }
+        }
+        Ok(PyDataFrame(DataFrame::new(columns)?))
+    }
+
+    /// a result that is a single node column: the series itself for one key column and a
+    /// frame of the key columns for composite keys
+    pub fn expand_series(&self, series: Series) -> Result<NodeColumn, GraphError> {
+        if !matches!(self.dtype, DataType::Struct(_)) {
+            return Ok(NodeColumn::Series(PySeries(series)));
+        }
+        let columns = series
+            .struct_()?
+            .fields_as_series()
+            .into_iter()
+            .map(Column::from)
+            .collect();
+        Ok(NodeColumn::Frame(PyDataFrame(DataFrame::new(columns)?)))
+    }
+}
+
+/// nodes returned on their own, see `NodeSchema::expand_series`
+#[derive(IntoPyObject)]
+pub enum NodeColumn {
+    Series(PySeries),
+    Frame(PyDataFrame),
+}
+
+impl NodeColumn {
+    /// the node series of a graph with a single key column
+    pub fn series(self) -> Option<Series> {
+        match self {
+            NodeColumn::Series(series) => Some(series.0),
+            NodeColumn::Frame(_) => None,
+        }
+    }
+
+    /// the key columns of a graph with composite keys
+    pub fn frame(self) -> Option<DataFrame> {
+        match self {
+            NodeColumn::Series(_) => None,
+            NodeColumn::Frame(df) => Some(df.0),
+        }
+    }
+}
+
+impl Default for NodeSchema {
+    fn default() -> Self {
+        NodeSchema {
+            dtype: DataType::String,
+        }
+    }
 }
 
 pub fn extract_node_data(series: &Series) -> Result<Vec<NodeData>, GraphError> {
-    Ok(match series.dtype() {
+    Ok(extract_optional_node_data(series)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// row aligned counterpart of `extract_node_data`, nulls are kept as `None`
+pub fn extract_optional_node_data(series: &Series) -> Result<Vec<Option<NodeData>>, GraphError> {
+    Ok(match node_key_dtype(series.dtype())? {
+        // a composite key with a null anywhere is a null key
+        DataType::Struct(_) => {
+            let fields = series
+                .struct_()?
+                .fields_as_series()
+                .iter()
+                .map(extract_optional_node_data)
+                .collect::<Result<Vec<_>, _>>()?;
+            let missing = series.is_null();
+            (0..series.len())
+                .map(|row| {
+                    if missing.get(row) == Some(true) {
+                        return None;
+                    }
+                    let parts: Option<Vec<NodeData>> =
+                        fields.iter().map(|f| f[row].clone()).collect();
+                    parts.map(NodeData::Tuple)
+                })
+                .collect()
+        }
         DataType::Int64 => series
+            .to_physical_repr()
+            .cast(&DataType::Int64)?
             .i64()?
             .into_iter()
-            .flatten()
-            .map(NodeData::Int)
+            .map(|v| v.map(NodeData::Int))
+            .collect(),
+        DataType::UInt64 => series
+            .u64()?
+            .into_iter()
+            .map(|v| {
+                v.map(|u| match i64::try_from(u) {
+                    Ok(i) => NodeData::Int(i),
+                    Err(_) => NodeData::UInt(u),
+                })
+            })
             .collect(),
         DataType::Float64 => series
+            .cast(&DataType::Float64)?
             .f64()?
             .into_iter()
-            .flatten()
-            .map(|f| NodeData::Float(OrderedFloat(f)))
+            .map(|v| v.map(|f| NodeData::Float(OrderedFloat(f))))
             .collect(),
         DataType::String => series
+            .cast(&DataType::String)?
             .str()?
             .into_iter()
-            .flatten()
-            .map(|s| NodeData::String(s.to_string()))
+            .map(|v| v.map(|s| NodeData::String(s.to_string())))
+            .collect(),
+        _ => series
+            .binary()?
+            .into_iter()
+            .map(|v| v.map(|b| NodeData::Binary(b.to_vec())))
             .collect(),
-        dt => {
-            return Err(GraphError::InvalidDataType(format!(
-                "Unsupported dtype:{}",
-                dt
-            )))
-        }
     })
 }
 
-#[pyclass]
-pub struct DirectedGraph {
-    graph: StableGraph<NodeData, f64, Directed>,
-    node_indices: HashMap<NodeData, NodeIndex>,
-}
-#[pymethods]
-impl DirectedGraph {
-    #[new]
-    pub fn new(
-        pydataframe: PyDataFrame,
-        sources_column: String,
-        sinks_column: String,
-        weights_column: Option<String>,
-    ) -> PyResult<Self> {
-        let dataframe = pydataframe.clone().0;
-
-        let source = dataframe
-            .column(&sources_column)
-            .map_err(GraphError::from)?;
-        let sink = dataframe.column(&sinks_column).map_err(GraphError::from)?;
-
-        if source.len() != sink.len() {
-            return Err(GraphError::MissingData("Missing Datapoints".into()).into());
-        }
-
-        let edge_weights;
-        let weights: &Series = match &weights_column {
-            Some(w) => dataframe
-                .column(w)
-                .map_err(GraphError::from)?
-                .as_materialized_series(),
-            None => {
-                edge_weights = Series::new("weights".into(), vec![1.0f64; source.len()]);
-                &edge_weights
-            }
-        };
+/// resolves a node value to its index in the graph
+pub fn lookup_node(
+    node_indices: &HashMap<NodeData, NodeIndex>,
+    node: &NodeData,
+) -> Result<NodeIndex, GraphError> {
+    node_indices
+        .get(node)
+        .copied()
+        .ok_or_else(|| GraphError::NodeNotFound(format!("{:?}", node)))
+}
+
+/// converts node indices back into a series of the graph's node dtype
+pub fn node_series<Ty: EdgeType>(
+    name: &str,
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    nodes: &[NodeIndex],
+) -> Result<Series, GraphError> {
+    let nodes: Vec<Option<NodeIndex>> = nodes.iter().copied().map(Some).collect();
+    nullable_node_series(name, graph, schema, &nodes)
+}
 
-        let weight_vec: Vec<f64> = weights
-            .f64()
-            .map_err(GraphError::from)?
+/// like `node_series`, with missing nodes written as nulls
+pub fn nullable_node_series<Ty: EdgeType>(
+    name: &str,
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    nodes: &[Option<NodeIndex>],
+) -> Result<Series, GraphError> {
+    let values: Vec<Option<&NodeData>> = nodes.iter().map(|n| n.map(|n| &graph[n])).collect();
+    node_values_series(name, schema, &values)
+}
+
+/// builds a series of the schema's dtype from node values
+pub fn node_values_series(
+    name: &str,
+    schema: &NodeSchema,
+    values: &[Option<&NodeData>],
+) -> Result<Series, GraphError> {
+    let mismatch = || GraphError::InvalidDataType("Nodes of mixed dtypes".into());
+    if let DataType::Struct(fields) = &schema.dtype {
+        let parts = fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let part: Vec<Option<&NodeData>> = values
+                    .iter()
+                    .map(|v| match v {
+                        Some(NodeData::Tuple(t)) => t.get(i).map(Some).ok_or_else(mismatch),
+                        None => Ok(None),
+                        _ => Err(mismatch()),
+                    })
+                    .collect::<Result<_, _>>()?;
+                node_values_series(field.name(), &NodeSchema::new(field.dtype())?, &part)
+            })
+            .collect::<Result<Vec<Series>, GraphError>>()?;
+        let validity = values.iter().map(|v| v.is_some()).collect();
+        return Ok(
+            StructChunked::from_series(name.into(), values.len(), parts.iter())?
+                .with_outer_validity(Some(validity))
+                .into_series(),
+        );
+    }
+    let values = values.iter().copied();
+    let physical = match node_key_dtype(&schema.dtype)? {
+        DataType::Int64 => Series::new(
+            name.into(),
+            values
+                .map(|v| match v {
+                    Some(NodeData::Int(i)) => Ok(Some(*i)),
+                    None => Ok(None),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<Vec<Option<i64>>, _>>()?,
+        ),
+        DataType::UInt64 => Series::new(
+            name.into(),
+            values
+                .map(|v| match v {
+                    Some(NodeData::Int(i)) => u64::try_from(*i).map(Some).map_err(|_| mismatch()),
+                    Some(NodeData::UInt(u)) => Ok(Some(*u)),
+                    None => Ok(None),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<Vec<Option<u64>>, _>>()?,
+        ),
+        DataType::Float64 => Series::new(
+            name.into(),
+            values
+                .map(|v| match v {
+                    Some(NodeData::Float(f)) => Ok(Some(f.into_inner())),
+                    None => Ok(None),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<Vec<Option<f64>>, _>>()?,
+        ),
+        DataType::String => Series::new(
+            name.into(),
+            values
+                .map(|v| match v {
+                    Some(NodeData::String(s)) => Ok(Some(s.as_str())),
+                    None => Ok(None),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<Vec<Option<&str>>, _>>()?,
+        ),
+        _ => Series::new(
+            name.into(),
+            values
+                .map(|v| match v {
+                    Some(NodeData::Binary(b)) => Ok(Some(b.as_slice())),
+                    None => Ok(None),
+                    _ => Err(mismatch()),
+                })
+                .collect::<Result<Vec<Option<&[u8]>>, _>>()?,
+        ),
+    };
+    // temporal dtypes only cast from their own physical width
+    let physical = if schema.dtype.is_temporal() {
+        physical.cast(&schema.dtype.to_physical())?
+    } else {
+        physical
+    };
+    Ok(physical.cast(&schema.dtype)?)
+}
+
+/// builds a list column holding one list of nodes per group
+pub fn node_list_series<Ty: EdgeType>(
+    name: &str,
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    groups: &[Vec<NodeIndex>],
+) -> Result<Series, GraphError> {
+    if groups.is_empty() {
+        let inner = node_series(name, graph, schema, &[])?.dtype().clone();
+        return Ok(Series::new_empty(
+            name.into(),
+            &DataType::List(Box::new(inner)),
+        ));
+    }
+    let lists = groups
+        .iter()
+        .map(|group| node_series("", graph, schema, group))
+        .collect::<Result<Vec<Series>, _>>()?;
+    Ok(Series::new(name.into(), lists))
+}
+
+/// builds a (step, source, target, weight) frame from an ordered walk over edges
+fn walk_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    walk: &[(NodeIndex, NodeIndex, EdgeIndex)],
+) -> Result<DataFrame, GraphError> {
+    let (sources, targets): (Vec<NodeIndex>, Vec<NodeIndex>) =
+        walk.iter().map(|&(a, b, _)| (a, b)).unzip();
+    let steps: Vec<u32> = (0..walk.len() as u32).collect();
+    let weights: Vec<f64> = walk.iter().map(|&(_, _, e)| graph[e]).collect();
+    Ok(DataFrame::new(vec![
+        Series::new("step".into(), steps).into(),
+        node_series("source", graph, schema, &sources)?.into(),
+        node_series("target", graph, schema, &targets)?.into(),
+        Series::new("weight".into(), weights).into(),
+    ])?)
+}
+
+/// builds a (node, depth) frame from nodes reached by a traversal
+fn depth_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    reached: Vec<(NodeIndex, u32)>,
+) -> Result<DataFrame, GraphError> {
+    let (nodes, depths): (Vec<NodeIndex>, Vec<u32>) = reached.into_iter().unzip();
+    Ok(DataFrame::new(vec![
+        node_series("node", graph, schema, &nodes)?.into(),
+        Series::new("depth".into(), depths).into(),
+    ])?)
+}
+
+/// smallest (radius) or largest (diameter) eccentricity with the nodes attaining it
+fn eccentricity_extreme<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    weighted: bool,
+    largest: bool,
+) -> Result<(f64, Vec<NodeIndex>), GraphError> {
+    let eccentricities = distance::eccentricities(graph, weighted)?;
+    let extreme = eccentricities
+        .iter()
+        .map(|&(_, e)| e)
+        .reduce(if largest { f64::max } else { f64::min })
+        .ok_or_else(|| GraphError::InvalidStructure("graph has no nodes".into()))?;
+    let nodes = eccentricities
+        .into_iter()
+        .filter(|&(_, e)| is_numerically_zero(e - extreme))
+        .map(|(n, _)| n)
+        .collect();
+    Ok((extreme, nodes))
+}
+
+fn eccentricity_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    weighted: bool,
+) -> Result<DataFrame, GraphError> {
+    let (nodes, values): (Vec<NodeIndex>, Vec<f64>) = distance::eccentricities(graph, weighted)?
+        .into_iter()
+        .unzip();
+    Ok(DataFrame::new(vec![
+        node_series("node", graph, schema, &nodes)?.into(),
+        Series::new("eccentricity".into(), values).into(),
+    ])?)
+}
+
+/// one column per link prediction metric, null where a pair could not be scored
+fn score_columns(scores: &[Option<PairScores>]) -> Vec<Column> {
+    let metric = |name: &str, f: fn(&PairScores) -> f64| {
+        let values: Vec<Option<f64>> = scores.iter().map(|s| s.as_ref().map(f)).collect();
+        Series::new(name.into(), values).into()
+    };
+    let common: Vec<Option<u32>> = scores
+        .iter()
+        .map(|s| s.as_ref().map(|s| s.common_neighbors))
+        .collect();
+    vec![
+        Series::new("common_neighbors".into(), common).into(),
+        metric("jaccard", |s| s.jaccard),
+        metric("adamic_adar", |s| s.adamic_adar),
+        metric("resource_allocation", |s| s.resource_allocation),
+        metric("preferential_attachment", |s| s.preferential_attachment),
+    ]
+}
+
+/// resolves every value of a node series, failing on nulls or unknown nodes
+pub fn lookup_nodes(
+    node_indices: &HashMap<NodeData, NodeIndex>,
+    series: &Series,
+) -> Result<Vec<NodeIndex>, GraphError> {
+    extract_optional_node_data(series)?
+        .into_iter()
+        .enumerate()
+        .map(|(row, node)| match node {
+            Some(node) => lookup_node(node_indices, &node),
+            None => Err(GraphError::NodeNotFound(format!(
+                "null node at row {}",
+                row
+            ))),
+        })
+        .collect()
+}
+
+fn random_walk_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    node_indices: &HashMap<NodeData, NodeIndex>,
+    start_nodes: Option<PySeries>,
+    params: WalkParams,
+    seed: Option<u64>,
+) -> Result<DataFrame, GraphError> {
+    if params.p <= 0.0 || params.q <= 0.0 {
+        return Err(GraphError::InvalidArgument(
+            "p and q must be positive".into(),
+        ));
+    }
+    if params.walk_length == 0 {
+        return Err(GraphError::InvalidArgument(
+            "walk_length must be at least 1".into(),
+        ));
+    }
+    distance::check_non_negative(graph)?;
+    let starts = match start_nodes {
+        Some(series) => lookup_nodes(node_indices, &series.0)?,
+        None => graph.node_indices().collect(),
+    };
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    let found = walks::random_walks(graph, &starts, &params, &mut rng);
+    let ids: Vec<u32> = (0..found.len() as u32).collect();
+    Ok(DataFrame::new(vec![
+        Series::new("walk_id".into(), ids).into(),
+        node_list_series("walk", graph, schema, &found)?.into(),
+    ])?)
+}
+
+/// builds a (node, depth, parent, order) frame from traversal visits in visit order
+fn visit_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    visits: &[Visit],
+) -> Result<DataFrame, GraphError> {
+    let nodes: Vec<NodeIndex> = visits.iter().map(|v| v.node).collect();
+    let depths: Vec<u32> = visits.iter().map(|v| v.depth).collect();
+    let parents: Vec<Option<NodeIndex>> = visits.iter().map(|v| v.parent).collect();
+    let order: Vec<u32> = (0..visits.len() as u32).collect();
+    Ok(DataFrame::new(vec![
+        node_series("node", graph, schema, &nodes)?.into(),
+        Series::new("depth".into(), depths).into(),
+        nullable_node_series("parent", graph, schema, &parents)?.into(),
+        Series::new("order".into(), order).into(),
+    ])?)
+}
+
+fn dfs_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    source: NodeIndex,
+    order: &str,
+) -> Result<DataFrame, GraphError> {
+    let postorder = match order {
+        "pre" => false,
+        "post" => true,
+        other => {
+            return Err(GraphError::InvalidArgument(format!(
+                "order must be \"pre\" or \"post\", got \"{}\"",
+                other
+            )))
+        }
+    };
+    visit_frame(graph, schema, &traversal::dfs(graph, source, postorder))
+}
+
+fn multi_source_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    node_indices: &HashMap<NodeData, NodeIndex>,
+    sources: &Series,
+    max_depth: Option<u32>,
+) -> Result<DataFrame, GraphError> {
+    let sources = lookup_nodes(node_indices, sources)?;
+    let (visits, origins): (Vec<Visit>, Vec<NodeIndex>) =
+        traversal::multi_source_bfs(graph, &sources, max_depth)
             .into_iter()
-            .flatten()
-            .collect();
+            .unzip();
+    let mut df = visit_frame(graph, schema, &visits)?;
+    df.with_column(node_series("source", graph, schema, &origins)?)?;
+    Ok(df)
+}
 
-        let mut graph = StableGraph::<NodeData, f64, Directed>::default();
-        let mut node_map = HashMap::new();
+/// answers row aligned reachability for a frame of pairs, null where a node is missing
+fn reachable_series<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    node_indices: &HashMap<NodeData, NodeIndex>,
+    index: Option<&ReachabilityIndex>,
+    pairs: &DataFrame,
+    left_column: &str,
+    right_column: &str,
+) -> Result<Series, GraphError> {
+    let left = pairs.column(left_column)?.as_materialized_series();
+    let right = pairs.column(right_column)?.as_materialized_series();
+    // a batch is worth indexing even when no index was built ahead of time
+    let batch_index;
+    let index = match index {
+        Some(index) => index,
+        None => {
+            batch_index = ReachabilityIndex::new(graph);
+            &batch_index
+        }
+    };
+    let reached: Vec<Option<bool>> = izip!(
+        extract_optional_node_data(left)?,
+        extract_optional_node_data(right)?
+    )
+    .map(|(a, b)| {
+        let a = a.and_then(|a| node_indices.get(&a).copied())?;
+        let b = b.and_then(|b| node_indices.get(&b).copied())?;
+        Some(index.reaches(a, b))
+    })
+    .collect();
+    Ok(Series::new("reachable".into(), reached))
+}
 
-        let source_nodes = extract_node_data(source.as_materialized_series())?;
-        let sink_nodes = extract_node_data(sink.as_materialized_series())?;
+/// builds a (cycle_id, cycle) frame with one list of nodes per cycle
+fn cycle_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    found: &[Vec<NodeIndex>],
+) -> Result<DataFrame, GraphError> {
+    let ids: Vec<u32> = (0..found.len() as u32).collect();
+    Ok(DataFrame::new(vec![
+        Series::new("cycle_id".into(), ids).into(),
+        node_list_series("cycle", graph, schema, found)?.into(),
+    ])?)
+}
 
-        for node_value in source_nodes.iter().chain(sink_nodes.iter()) {
-            if !node_map.contains_key(node_value) {
-                let node_index = graph.add_node(node_value.clone());
-                node_map.insert(node_value.clone(), node_index);
-            }
+/// row aligned shortest path lengths for a frame of pairs, pairs sharing a source reuse
+/// one full search tree while lone pairs use a bidirectional search
+fn path_length_series<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    node_indices: &HashMap<NodeData, NodeIndex>,
+    pairs: &DataFrame,
+    left_column: &str,
+    right_column: &str,
+) -> Result<Series, GraphError> {
+    distance::check_non_negative(graph)?;
+    let left = pairs.column(left_column)?.as_materialized_series();
+    let right = pairs.column(right_column)?.as_materialized_series();
+    let rows: Vec<Option<(NodeIndex, NodeIndex)>> = izip!(
+        extract_optional_node_data(left)?,
+        extract_optional_node_data(right)?
+    )
+    .map(|(a, b)| {
+        let a = a.and_then(|a| node_indices.get(&a).copied())?;
+        let b = b.and_then(|b| node_indices.get(&b).copied())?;
+        Some((a, b))
+    })
+    .collect();
+
+    let mut order: Vec<(NodeIndex, NodeIndex, usize)> = rows
+        .iter()
+        .enumerate()
+        .filter_map(|(row, pair)| pair.map(|(a, b)| (a, b, row)))
+        .collect();
+    order.sort_unstable_by_key(|&(a, _, _)| a);
+
+    let mut lengths: Vec<Option<f64>> = vec![None; rows.len()];
+    for group in order.chunk_by(|x, y| x.0 == y.0) {
+        if let [(source, target, row)] = group {
+            lengths[*row] =
+                distance::bidirectional_dijkstra(graph, *source, *target).map(|(l, _)| l);
+            continue;
         }
+        let tree = ShortestPathTree::new(graph, group[0].0);
+        for &(_, target, row) in group {
+            lengths[row] = tree.distance.get(&target).copied();
+        }
+    }
+    Ok(Series::new("distance".into(), lengths))
+}
+
+fn shortest_path_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    source: NodeIndex,
+    target: NodeIndex,
+    costs: &NodeCosts,
+) -> Result<DataFrame, GraphError> {
+    let (_, steps) = distance::shortest_path(graph, source, target, costs)?
+        .ok_or_else(|| GraphError::NoSolution("target is not reachable from source".into()))?;
+    walk_frame(graph, schema, &steps)
+}
+
+/// numeric values of `column` cast to f64, nulls are rejected with their positions
+pub fn float_values(values: &Series, column: &str) -> Result<Vec<f64>, GraphError> {
+    let values = values.cast(&DataType::Float64)?;
+    let values = values.f64()?;
+    if values.null_count() > 0 {
+        let rows = values
+            .iter()
+            .enumerate()
+            .filter_map(|(row, value)| value.is_none().then_some(row))
+            .collect();
+        return Err(GraphError::InvalidRows(
+            format!("column {} contains nulls", column),
+            rows,
+        ));
+    }
+    Ok(values.into_no_null_iter().collect())
+}
+
+/// attribute rows of the given nodes, all null for nodes without attributes
+fn node_attributes(
+    node_rows: &AttributeRows,
+    nodes: &[NodeIndex],
+) -> Result<DataFrame, GraphError> {
+    let indices: Vec<usize> = nodes.iter().map(|n| n.index()).collect();
+    node_rows.take(&indices)
+}
 
-        for (src, dest, wght) in izip!(source_nodes.iter(), sink_nodes.iter(), weight_vec.iter()) {
-            let src_index = node_map[src];
-            let dest_index = node_map[dest];
-            graph.add_edge(src_index, dest_index, *wght);
+/// attribute columns whose names `frame` already has, prefixed with `attr_` until they
+/// are free, so an attribute called like a result column does not clash with it
+fn prefixed_attributes(
+    frame: &DataFrame,
+    mut attributes: DataFrame,
+) -> Result<DataFrame, GraphError> {
+    for name in attributes.get_column_names_owned() {
+        if frame.column(&name).is_err() {
+            continue;
         }
+        let mut renamed = format!("attr_{}", name);
+        while frame.column(&renamed).is_ok() || attributes.column(&renamed).is_ok() {
+            renamed.insert_str(0, "attr_");
+        }
+        attributes.rename(&name, renamed.into())?;
+    }
+    Ok(attributes)
+}
 
-        Ok(DirectedGraph {
-            graph,
-            node_indices: node_map,
-        })
+/// every node with its attributes
+fn nodes_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    node_rows: &AttributeRows,
+) -> Result<DataFrame, GraphError> {
+    let nodes: Vec<NodeIndex> = graph.node_indices().collect();
+    let mut df = DataFrame::new(vec![node_series("node", graph, schema, &nodes)?.into()])?;
+    let attributes = prefixed_attributes(&df, node_attributes(node_rows, &nodes)?)?;
+    df.hstack_mut(attributes.get_columns())?;
+    Ok(df)
+}
+
+/// numeric node attribute per node index cast to f64, nodes without a value get zero
+fn node_values(
+    node_rows: &AttributeRows,
+    bound: usize,
+    column: &str,
+) -> Result<Vec<f64>, GraphError> {
+    let indices: Vec<usize> = (0..bound).collect();
+    let values = node_rows
+        .column(column, &indices)?
+        .cast(&DataType::Float64)?;
+    Ok(values.f64()?.iter().map(|v| v.unwrap_or(0.0)).collect())
+}
+
+/// boolean node attribute per node index, nodes without a value are false
+fn node_flags(
+    node_rows: &AttributeRows,
+    bound: usize,
+    column: &str,
+) -> Result<Vec<bool>, GraphError> {
+    let indices: Vec<usize> = (0..bound).collect();
+    let values = node_rows.column(column, &indices)?;
+    if values.dtype() != &DataType::Boolean {
+        return Err(GraphError::InvalidDataType(format!(
+            "node filter column {} must be boolean, got {}",
+            column,
+            values.dtype()
+        )));
     }
+    Ok(values.bool()?.iter().map(|v| v.unwrap_or(false)).collect())
 }
 
-#[pyclass]
-pub struct UndirectedGraph {
-    graph: StableGraph<NodeData, f64, Undirected>,
-    node_indices: HashMap<NodeData, NodeIndex>,
+/// the node costs of a point to point search read from node attribute columns
+fn node_costs(
+    node_rows: &AttributeRows,
+    bound: usize,
+    heuristic: Option<&str>,
+    node_weight: Option<&str>,
+    node_filter: Option<&str>,
+) -> Result<NodeCosts, GraphError> {
+    Ok(NodeCosts {
+        heuristic: heuristic
+            .map(|column| node_values(node_rows, bound, column))
+            .transpose()?,
+        weights: node_weight
+            .map(|column| node_values(node_rows, bound, column))
+            .transpose()?,
+        allowed: node_filter
+            .map(|column| node_flags(node_rows, bound, column))
+            .transpose()?,
+    })
 }
-#[pymethods]
-impl UndirectedGraph {
-    #[new]
-    pub fn new(
-        pydataframe: PyDataFrame,
-        sources_column: String,
-        sinks_column: String,
-        weights_column: Option<String>,
-    ) -> PyResult<Self> {
-        let dataframe = pydataframe.clone().0;
 
-        let source = dataframe
-            .column(&sources_column)
-            .map_err(GraphError::from)?;
-        let sink = dataframe.column(&sinks_column).map_err(GraphError::from)?;
+/// the graph with its edge weights taken from an edge attribute column, or as stored
+fn reweighted<'a, Ty: EdgeType>(
+    graph: &'a StableGraph<NodeData, f64, Ty>,
+    edge_attributes: &EdgeAttributes,
+    weight: Option<&str>,
+) -> Result<Cow<'a, StableGraph<NodeData, f64, Ty>>, GraphError> {
+    // the stored weights may have been updated since the weight column was read
+    let column = match weight {
+        Some(column) if Some(column) != edge_attributes.weight_column() => column,
+        _ => return Ok(Cow::Borrowed(graph)),
+    };
+    let edges: Vec<EdgeIndex> = graph.edge_indices().collect();
+    let mut by_index = vec![0.0; graph.edge_bound()];
+    for (edge, value) in edges.iter().zip(edge_attributes.values(column, &edges)?) {
+        by_index[edge.index()] = value;
+    }
+    Ok(Cow::Owned(graph.map(
+        |_, node| node.clone(),
+        |edge, _| by_index[edge.index()],
+    )))
+}
+
+/// the graph a distance measure runs on, a weight column only makes sense to a weighted one
+fn distance_graph<'a, Ty: EdgeType>(
+    graph: &'a StableGraph<NodeData, f64, Ty>,
+    edge_attributes: &EdgeAttributes,
+    weighted: bool,
+    weight: Option<&str>,
+) -> Result<Cow<'a, StableGraph<NodeData, f64, Ty>>, GraphError> {
+    if !weighted && weight.is_some() {
+        return Err(GraphError::InvalidArgument(
+            "weight is only read when weighted is true".into(),
+        ));
+    }
+    reweighted(graph, edge_attributes, weight)
+}
 
-        if source.len() != sink.len() {
-            return Err(GraphError::MissingData("Missing Datapoints".into()).into());
+/// every edge with its weight and attribute columns
+fn edges_frame<Ty: EdgeType>(
+    graph: &StableGraph<NodeData, f64, Ty>,
+    schema: &NodeSchema,
+    edge_attributes: &EdgeAttributes,
+) -> Result<DataFrame, GraphError> {
+    let edges: Vec<EdgeIndex> = graph.edge_indices().collect();
+    let (sources, targets): (Vec<NodeIndex>, Vec<NodeIndex>) = edges
+        .iter()
+        .map(|&e| graph.edge_endpoints(e).expect("edge exists"))
+        .unzip();
+    let weights: Vec<f64> = edges.iter().map(|&e| graph[e]).collect();
+    let mut df = DataFrame::new(vec![
+        node_series("source", graph, schema, &sources)?.into(),
+        node_series("target", graph, schema, &targets)?.into(),
+        Series::new("weight".into(), weights).into(),
+    ])?;
+    let attributes = prefixed_attributes(&df, edge_attributes.rows_of(&edges)?)?;
+    df.hstack_mut(attributes.get_columns())?;
+    Ok(df)
+}
+
+/// declares a graph pyclass over `GraphCore<$edge_type>` with the methods every graph has,
+/// followed by `$methods`, the ones only this class has. pyo3 takes a single
+/// `#[pymethods]` block per class, so the shared methods are spliced in here rather than
+/// written once per class
+macro_rules! graph_class {
+    ($class:ident, $edge_type:ty, { $($methods:tt)* }) => {
+        /// a graph read from an edge frame, eager or lazy. keys and weights are column names
+        /// or expressions, which are evaluated again on the frames given to add_edges,
+        /// remove_edges and update_weights. `attributes` names the columns kept with the
+        /// edges: by default every other column of an eager frame and none of a lazy one, so
+        /// a lazy frame only collects the columns the graph needs
+        #[pyclass]
+        pub struct $class {
+            core: GraphCore<$edge_type>,
+            reachability: Option<ReachabilityIndex>,
         }
 
-        let edge_weights;
-        let weights: &Series = match &weights_column {
-            Some(w) => dataframe
-                .column(w)
-                .map_err(GraphError::from)?
-                .as_materialized_series(),
-            None => {
-                edge_weights = Series::new("weights".into(), vec![1.0f64; source.len()]);
-                &edge_weights
+        impl $class {
+            /// builds the graph with the default options
+            pub fn new(
+                pydataframe: PyDataFrame,
+                sources_column: String,
+                sinks_column: String,
+                weights_column: Option<String>,
+            ) -> PyResult<Self> {
+                Self::with_options(
+                    pydataframe,
+                    sources_column.into(),
+                    sinks_column.into(),
+                    weights_column,
+                    GraphOptions::default(),
+                )
             }
-        };
 
-        let weight_vec: Vec<f64> = weights
-            .f64()
-            .map_err(GraphError::from)?
-            .into_iter()
-            .flatten()
-            .collect();
+            pub fn with_options(
+                pydataframe: PyDataFrame,
+                sources_column: KeyColumns,
+                sinks_column: KeyColumns,
+                weights_column: Option<String>,
+                options: GraphOptions,
+            ) -> PyResult<Self> {
+                let core = GraphCore::build(
+                    &pydataframe.0,
+                    sources_column,
+                    sinks_column,
+                    weights_column,
+                    options,
+                )?;
+                Ok($class {
+                    core,
+                    reachability: None,
+                })
+            }
+        }
 
-        let mut graph = StableGraph::<NodeData, f64, Undirected>::default();
-        let mut node_map = HashMap::new();
+        #[pymethods]
+        impl $class {
+            #[new]
+            #[pyo3(signature = (pydataframe, sources_column, sinks_column, weights_column=None, null_policy=String::from("error"), nodes=None, node_key=None, default_weight=None, validate_weights=Vec::new(), multi_edges=String::from("keep"), self_loops=String::from("keep"), attributes=None))]
+            #[allow(clippy::too_many_arguments)]
+            fn py_new(
+                pydataframe: FrameInput,
+                sources_column: KeySpec,
+                sinks_column: KeySpec,
+                weights_column: Option<WeightSpec>,
+                null_policy: String,
+                nodes: Option<FrameInput>,
+                node_key: Option<KeyColumns>,
+                default_weight: Option<f64>,
+                validate_weights: Vec<String>,
+                multi_edges: String,
+                self_loops: String,
+                attributes: Option<Vec<String>>,
+            ) -> PyResult<Self> {
+                let mut options = GraphOptions::from_py(
+                    &null_policy,
+                    nodes,
+                    node_key,
+                    default_weight,
+                    &validate_weights,
+                    &multi_edges,
+                    &self_loops,
+                )?;
+                let input = ingest::collect_edge_input(
+                    pydataframe,
+                    sources_column,
+                    sinks_column,
+                    weights_column,
+                    attributes,
+                )?;
+                options.expressions = input.expressions;
+                Self::with_options(
+                    PyDataFrame(input.frame),
+                    input.sources_column,
+                    input.sinks_column,
+                    input.weights_column,
+                    options,
+                )
+            }
 
-        let source_nodes = extract_node_data(source.as_materialized_series())?;
-        let sink_nodes = extract_node_data(sink.as_materialized_series())?;
+            /// every node with its attributes, an attribute named `node` comes out as
+            /// `attr_node`
+            pub fn nodes(&self) -> PyResult<PyDataFrame> {
+                let df = nodes_frame(
+                    &self.core.graph,
+                    &self.core.node_schema,
+                    &self.core.node_rows,
+                )?;
+                Ok(self.core.node_schema.expand(df)?)
+            }
 
-        for node_value in source_nodes.iter().chain(sink_nodes.iter()) {
-            if !node_map.contains_key(node_value) {
-                let node_index = graph.add_node(node_value.clone());
-                node_map.insert(node_value.clone(), node_index);
+            /// every edge with its weight and attributes, attributes named `source`,
+            /// `target` or `weight` come out prefixed with `attr_`
+            pub fn edges(&self) -> PyResult<PyDataFrame> {
+                let df = edges_frame(
+                    &self.core.graph,
+                    &self.core.node_schema,
+                    &self.core.edge_attributes,
+                )?;
+                Ok(self.core.node_schema.expand(df)?)
+            }
+
+            pub fn has_eulerian_path(&self) -> bool {
+                eulerian::eulerian_kind(&self.core.graph) != EulerianKind::NotEulerian
+            }
+
+            pub fn has_eulerian_circuit(&self) -> bool {
+                eulerian::eulerian_kind(&self.core.graph) == EulerianKind::Circuit
+            }
+
+            #[pyo3(signature = (start=None))]
+            pub fn eulerian_path(&self, start: Option<NodeArg>) -> PyResult<PyDataFrame> {
+                let start = start
+                    .map(|node| self.core.node_index(node))
+                    .transpose()?;
+                let walk = eulerian::eulerian_path(&self.core.graph, start)?;
+                Ok(self.core.node_schema.expand(walk_frame(
+                    &self.core.graph,
+                    &self.core.node_schema,
+                    &walk,
+                )?)?)
+            }
+
+            #[pyo3(signature = (weighted=false, weight=None))]
+            pub fn eccentricity(&self, weighted: bool, weight: Option<String>) -> PyResult<PyDataFrame> {
+                let graph = distance_graph(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weighted,
+                    weight.as_deref(),
+                )?;
+                Ok(self.core.node_schema.expand(eccentricity_frame(
+                    &graph,
+                    &self.core.node_schema,
+                    weighted,
+                )?)?)
+            }
+
+            #[pyo3(signature = (weighted=false, approximate=false, weight=None))]
+            pub fn diameter(
+                &self,
+                weighted: bool,
+                approximate: bool,
+                weight: Option<String>,
+            ) -> PyResult<f64> {
+                let graph = distance_graph(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weighted,
+                    weight.as_deref(),
+                )?;
+                if approximate {
+                    return Ok(distance::double_sweep_diameter(&graph, weighted)?);
+                }
+                Ok(eccentricity_extreme(&graph, weighted, true)?.0)
+            }
+
+            #[pyo3(signature = (weighted=false, weight=None))]
+            pub fn radius(&self, weighted: bool, weight: Option<String>) -> PyResult<f64> {
+                let graph = distance_graph(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weighted,
+                    weight.as_deref(),
+                )?;
+                Ok(eccentricity_extreme(&graph, weighted, false)?.0)
+            }
+
+            #[pyo3(signature = (weighted=false, weight=None))]
+            pub fn center(&self, weighted: bool, weight: Option<String>) -> PyResult<NodeColumn> {
+                let graph = distance_graph(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weighted,
+                    weight.as_deref(),
+                )?;
+                let (_, nodes) = eccentricity_extreme(&graph, weighted, false)?;
+                Ok(self.core.node_schema.expand_series(node_series(
+                    "node",
+                    &graph,
+                    &self.core.node_schema,
+                    &nodes,
+                )?)?)
+            }
+
+            #[pyo3(signature = (weighted=false, weight=None))]
+            pub fn periphery(
+                &self,
+                weighted: bool,
+                weight: Option<String>,
+            ) -> PyResult<NodeColumn> {
+                let graph = distance_graph(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weighted,
+                    weight.as_deref(),
+                )?;
+                let (_, nodes) = eccentricity_extreme(&graph, weighted, true)?;
+                Ok(self.core.node_schema.expand_series(node_series(
+                    "node",
+                    &graph,
+                    &self.core.node_schema,
+                    &nodes,
+                )?)?)
+            }
+
+            #[pyo3(signature = (start_nodes=None, walk_length=80, walks_per_node=10, p=1.0, q=1.0, seed=None, weight=None))]
+            #[allow(clippy::too_many_arguments)]
+            pub fn random_walks(
+                &self,
+                start_nodes: Option<PySeries>,
+                walk_length: usize,
+                walks_per_node: usize,
+                p: f64,
+                q: f64,
+                seed: Option<u64>,
+                weight: Option<String>,
+            ) -> PyResult<PyDataFrame> {
+                let graph = reweighted(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weight.as_deref(),
+                )?;
+                let params = WalkParams {
+                    walk_length,
+                    walks_per_node,
+                    p,
+                    q,
+                };
+                let df = random_walk_frame(
+                    &graph,
+                    &self.core.node_schema,
+                    &self.core.node_indices,
+                    start_nodes,
+                    params,
+                    seed,
+                )?;
+                Ok(self.core.node_schema.expand(df)?)
+            }
+
+            #[pyo3(signature = (source, max_depth=None))]
+            pub fn bfs(&self, source: NodeArg, max_depth: Option<u32>) -> PyResult<PyDataFrame> {
+                let source = self.core.node_index(source)?;
+                let visits = traversal::bfs(&self.core.graph, source, max_depth);
+                Ok(self.core.node_schema.expand(visit_frame(
+                    &self.core.graph,
+                    &self.core.node_schema,
+                    &visits,
+                )?)?)
+            }
+
+            #[pyo3(signature = (source, order="pre"))]
+            pub fn dfs(&self, source: NodeArg, order: &str) -> PyResult<PyDataFrame> {
+                let source = self.core.node_index(source)?;
+                Ok(self.core.node_schema.expand(dfs_frame(
+                    &self.core.graph,
+                    &self.core.node_schema,
+                    source,
+                    order,
+                )?)?)
+            }
+
+            #[pyo3(signature = (sources, max_depth=None))]
+            pub fn multi_source_bfs(
+                &self,
+                sources: PySeries,
+                max_depth: Option<u32>,
+            ) -> PyResult<PyDataFrame> {
+                let df = multi_source_frame(
+                    &self.core.graph,
+                    &self.core.node_schema,
+                    &self.core.node_indices,
+                    &sources.0,
+                    max_depth,
+                )?;
+                Ok(self.core.node_schema.expand(df)?)
+            }
+
+            pub fn has_path(&self, source: NodeArg, target: NodeArg) -> PyResult<bool> {
+                let source = self.core.node_index(source)?;
+                let target = self.core.node_index(target)?;
+                Ok(match &self.reachability {
+                    Some(index) => index.reaches(source, target),
+                    None => has_path_connecting(&self.core.graph, source, target, None),
+                })
+            }
+
+            pub fn build_reachability_index(&mut self) {
+                self.reachability = Some(ReachabilityIndex::new(&self.core.graph));
+            }
+
+            /// adds the edges of a frame with the graph's input columns
+            pub fn add_edges(&mut self, pydataframe: PyDataFrame) -> PyResult<()> {
+                self.reachability = None;
+                Ok(self.core.add_edges(&pydataframe.0)?)
+            }
+
+            /// adds the nodes of a node frame, nodes already in the graph take its attributes
+            pub fn add_nodes(&mut self, pydataframe: PyDataFrame) -> PyResult<()> {
+                self.reachability = None;
+                Ok(self.core.add_nodes(&pydataframe.0)?)
+            }
+
+            /// removes every edge between the source and sink of each row, returns the count removed
+            pub fn remove_edges(&mut self, pydataframe: PyDataFrame) -> PyResult<usize> {
+                self.reachability = None;
+                Ok(self.core.remove_edges(&pydataframe.0)?)
+            }
+
+            /// removes the nodes and their edges, returns the count of nodes removed
+            pub fn remove_nodes(&mut self, nodes: PySeries) -> PyResult<usize> {
+                self.reachability = None;
+                Ok(self.core.remove_nodes(&nodes.0)?)
+            }
+
+            /// sets the weights of existing edges, read from `weights_column` or the graph's
+            /// own. refused when multi_edges aggregates, as one weight cannot stand in for
+            /// merged rows
+            #[pyo3(signature = (pydataframe, weights_column=None))]
+            pub fn update_weights(
+                &mut self,
+                pydataframe: PyDataFrame,
+                weights_column: Option<String>,
+            ) -> PyResult<()> {
+                Ok(self.core.update_weights(&pydataframe.0, weights_column)?)
+            }
+
+            pub fn reachable(
+                &self,
+                pairs: PyDataFrame,
+                left_column: String,
+                right_column: String,
+            ) -> PyResult<PySeries> {
+                Ok(PySeries(reachable_series(
+                    &self.core.graph,
+                    &self.core.node_indices,
+                    self.reachability.as_ref(),
+                    &pairs.0,
+                    &left_column,
+                    &right_column,
+                )?))
+            }
+
+            /// the steps of a shortest path. `heuristic`, `node_weight` and `node_filter` name
+            /// node attribute columns: an a* estimate of the cost left to the target, a cost
+            /// added for every node the path enters, and the boolean column of nodes the path
+            /// may use, where nodes without a value are left out
+            #[pyo3(signature = (source, target, weight=None, heuristic=None, node_weight=None, node_filter=None))]
+            pub fn shortest_path(
+                &self,
+                source: NodeArg,
+                target: NodeArg,
+                weight: Option<String>,
+                heuristic: Option<String>,
+                node_weight: Option<String>,
+                node_filter: Option<String>,
+            ) -> PyResult<PyDataFrame> {
+                let graph = reweighted(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weight.as_deref(),
+                )?;
+                let costs = node_costs(
+                    &self.core.node_rows,
+                    self.core.graph.node_bound(),
+                    heuristic.as_deref(),
+                    node_weight.as_deref(),
+                    node_filter.as_deref(),
+                )?;
+                let source = self.core.node_index(source)?;
+                let target = self.core.node_index(target)?;
+                Ok(self.core.node_schema.expand(shortest_path_frame(
+                    &graph,
+                    &self.core.node_schema,
+                    source,
+                    target,
+                    &costs,
+                )?)?)
+            }
+
+            /// the length of a shortest path, node weights included, see `shortest_path`
+            #[pyo3(signature = (source, target, weight=None, heuristic=None, node_weight=None, node_filter=None))]
+            pub fn shortest_path_length(
+                &self,
+                source: NodeArg,
+                target: NodeArg,
+                weight: Option<String>,
+                heuristic: Option<String>,
+                node_weight: Option<String>,
+                node_filter: Option<String>,
+            ) -> PyResult<Option<f64>> {
+                let graph = reweighted(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weight.as_deref(),
+                )?;
+                let costs = node_costs(
+                    &self.core.node_rows,
+                    self.core.graph.node_bound(),
+                    heuristic.as_deref(),
+                    node_weight.as_deref(),
+                    node_filter.as_deref(),
+                )?;
+                let source = self.core.node_index(source)?;
+                let target = self.core.node_index(target)?;
+                Ok(distance::shortest_path(&graph, source, target, &costs)?.map(|(l, _)| l))
             }
-        }
 
-        for (src, dest, wght) in izip!(source_nodes.iter(), sink_nodes.iter(), weight_vec.iter()) {
-            let src_index = node_map[src];
-            let dest_index = node_map[dest];
-            graph.add_edge(src_index, dest_index, *wght);
+            #[pyo3(signature = (pairs, left_column, right_column, weight=None))]
+            pub fn shortest_path_lengths(
+                &self,
+                pairs: PyDataFrame,
+                left_column: String,
+                right_column: String,
+                weight: Option<String>,
+            ) -> PyResult<PySeries> {
+                let graph = reweighted(
+                    &self.core.graph,
+                    &self.core.edge_attributes,
+                    weight.as_deref(),
+                )?;
+                Ok(PySeries(path_length_series(
+                    &graph,
+                    &self.core.node_indices,
+                    &pairs.0,
+                    &left_column,
+                    &right_column,
+                )?))
+            }
+
+            $($methods)*
         }
+    };
+}
 
-        Ok(UndirectedGraph {
-            graph,
-            node_indices: node_map,
-        })
+graph_class!(DirectedGraph, Directed, {
+    #[pyo3(signature = (node, max_depth=None))]
+    pub fn ancestors(&self, node: NodeArg, max_depth: Option<u32>) -> PyResult<PyDataFrame> {
+        let node = self.core.node_index(node)?;
+        let reached =
+            ancestry::reachable_within(&self.core.graph, node, Direction::Incoming, max_depth);
+        Ok(self.core.node_schema.expand(depth_frame(
+            &self.core.graph,
+            &self.core.node_schema,
+            reached,
+        )?)?)
     }
-}
+
+    #[pyo3(signature = (node, max_depth=None))]
+    pub fn descendants(&self, node: NodeArg, max_depth: Option<u32>) -> PyResult<PyDataFrame> {
+        let node = self.core.node_index(node)?;
+        let reached =
+            ancestry::reachable_within(&self.core.graph, node, Direction::Outgoing, max_depth);
+        Ok(self.core.node_schema.expand(depth_frame(
+            &self.core.graph,
+            &self.core.node_schema,
+            reached,
+        )?)?)
+    }
+
+    pub fn lowest_common_ancestor(
+        &self,
+        pairs: PyDataFrame,
+        left_column: String,
+        right_column: String,
+    ) -> PyResult<NodeColumn> {
+        let pairs = pairs.0;
+        let left = pairs.column(&left_column).map_err(GraphError::from)?;
+        let right = pairs.column(&right_column).map_err(GraphError::from)?;
+        let left = extract_optional_node_data(left.as_materialized_series())?;
+        let right = extract_optional_node_data(right.as_materialized_series())?;
+
+        let mut index = AncestorIndex::new(&self.core.graph)?;
+        let ancestors: Vec<Option<NodeIndex>> = left
+            .iter()
+            .zip(right.iter())
+            .map(|(a, b)| {
+                let a = a.as_ref().and_then(|a| self.core.node_indices.get(a))?;
+                let b = b.as_ref().and_then(|b| self.core.node_indices.get(b))?;
+                index.lowest_common_ancestor(*a, *b)
+            })
+            .collect();
+        Ok(self.core.node_schema.expand_series(nullable_node_series(
+            "lca",
+            &self.core.graph,
+            &self.core.node_schema,
+            &ancestors,
+        )?)?)
+    }
+
+    pub fn is_cyclic(&self) -> bool {
+        is_cyclic_directed(&self.core.graph)
+    }
+
+    #[pyo3(signature = (limit=None))]
+    pub fn simple_cycles(&self, limit: Option<usize>) -> PyResult<PyDataFrame> {
+        let found = cycles::simple_cycles(&self.core.graph, limit);
+        Ok(self.core.node_schema.expand(cycle_frame(
+            &self.core.graph,
+            &self.core.node_schema,
+            &found,
+        )?)?)
+    }
+
+    pub fn is_tree(&self) -> bool {
+        trees::is_tree(&self.core.graph)
+    }
+
+    pub fn is_forest(&self) -> bool {
+        trees::is_forest(&self.core.graph)
+    }
+
+    pub fn roots(&self) -> PyResult<NodeColumn> {
+        let roots = trees::roots(&self.core.graph);
+        Ok(self.core.node_schema.expand_series(node_series(
+            "node",
+            &self.core.graph,
+            &self.core.node_schema,
+            &roots,
+        )?)?)
+    }
+
+    pub fn leaves(&self) -> PyResult<NodeColumn> {
+        let leaves = trees::leaves(&self.core.graph);
+        Ok(self.core.node_schema.expand_series(node_series(
+            "node",
+            &self.core.graph,
+            &self.core.node_schema,
+            &leaves,
+        )?)?)
+    }
+
+    pub fn depth(&self) -> PyResult<PyDataFrame> {
+        let reached = trees::depths(&self.core.graph)?;
+        Ok(self.core.node_schema.expand(depth_frame(
+            &self.core.graph,
+            &self.core.node_schema,
+            reached,
+        )?)?)
+    }
+
+    pub fn subtree_sizes(&self) -> PyResult<PyDataFrame> {
+        let (nodes, sizes): (Vec<NodeIndex>, Vec<u32>) =
+            trees::subtree_sizes(&self.core.graph)?.into_iter().unzip();
+        let df = DataFrame::new(vec![
+            node_series("node", &self.core.graph, &self.core.node_schema, &nodes)?.into(),
+            Series::new("size".into(), sizes).into(),
+        ])
+        .map_err(GraphError::from)?;
+        Ok(self.core.node_schema.expand(df)?)
+    }
+
+    #[pyo3(signature = (capacity_column, cost_column, supplies=None, node_column=String::from("node"), supply_column=String::from("supply")))]
+    pub fn min_cost_flow(
+        &self,
+        capacity_column: String,
+        cost_column: String,
+        supplies: Option<PyDataFrame>,
+        node_column: String,
+        supply_column: String,
+    ) -> PyResult<PyDataFrame> {
+        // without a supplies frame the supply is a node attribute
+        let supply = match supplies {
+            Some(supplies) => {
+                let supplies = supplies.0;
+                let nodes = supplies
+                    .column(&node_column)
+                    .map_err(GraphError::from)?
+                    .as_materialized_series();
+                let nodes = lookup_nodes(&self.core.node_indices, nodes)?;
+                let amounts = supplies
+                    .column(&supply_column)
+                    .map_err(GraphError::from)?
+                    .as_materialized_series();
+                let amounts = float_values(amounts, &supply_column)?;
+                let mut supply = vec![0.0; self.core.graph.node_bound()];
+                for (node, amount) in nodes.iter().zip(amounts) {
+                    supply[node.index()] += amount;
+                }
+                supply
+            }
+            None => node_values(
+                &self.core.node_rows,
+                self.core.graph.node_bound(),
+                &supply_column,
+            )?,
+        };
+
+        let edges: Vec<_> = self.core.graph.edge_references().collect();
+        let ids: Vec<EdgeIndex> = edges.iter().map(|e| e.id()).collect();
+        let capacities = self.core.edge_attributes.values(&capacity_column, &ids)?;
+        let costs = self.core.edge_attributes.values(&cost_column, &ids)?;
+        let arcs: Vec<FlowArc> = izip!(&edges, capacities, costs)
+            .map(|(e, capacity, cost)| FlowArc {
+                from: e.source().index(),
+                to: e.target().index(),
+                capacity,
+                cost,
+            })
+            .collect();
+        let flows = flow::min_cost_flow(self.core.graph.node_bound(), &arcs, &supply)?;
+
+        let sources: Vec<NodeIndex> = edges.iter().map(|e| e.source()).collect();
+        let targets: Vec<NodeIndex> = edges.iter().map(|e| e.target()).collect();
+        let flow_costs: Vec<f64> = flows.iter().zip(&arcs).map(|(f, a)| f * a.cost).collect();
+        let df = DataFrame::new(vec![
+            node_series("source", &self.core.graph, &self.core.node_schema, &sources)?.into(),
+            node_series("target", &self.core.graph, &self.core.node_schema, &targets)?.into(),
+            Series::new("flow".into(), flows).into(),
+            Series::new("flow_cost".into(), flow_costs).into(),
+        ])
+        .map_err(GraphError::from)?;
+        Ok(self.core.node_schema.expand(df)?)
+    }
+
+    pub fn immediate_dominators(&self, root: NodeArg) -> PyResult<PyDataFrame> {
+        let root = self.core.node_index(root)?;
+        let (nodes, idoms): (Vec<NodeIndex>, Vec<Option<NodeIndex>>) =
+            dominators::immediate_dominators(&self.core.graph, root)
+                .into_iter()
+                .unzip();
+        let df = DataFrame::new(vec![
+            node_series("node", &self.core.graph, &self.core.node_schema, &nodes)?.into(),
+            nullable_node_series("idom", &self.core.graph, &self.core.node_schema, &idoms)?.into(),
+        ])
+        .map_err(GraphError::from)?;
+        Ok(self.core.node_schema.expand(df)?)
+    }
+
+    pub fn dominance_frontier(&self, root: NodeArg) -> PyResult<PyDataFrame> {
+        let root = self.core.node_index(root)?;
+        let (nodes, frontier): (Vec<NodeIndex>, Vec<NodeIndex>) =
+            dominators::dominance_frontiers(&self.core.graph, root)
+                .into_iter()
+                .unzip();
+        let df = DataFrame::new(vec![
+            node_series("node", &self.core.graph, &self.core.node_schema, &nodes)?.into(),
+            node_series(
+                "frontier",
+                &self.core.graph,
+                &self.core.node_schema,
+                &frontier,
+            )?
+            .into(),
+        ])
+        .map_err(GraphError::from)?;
+        Ok(self.core.node_schema.expand(df)?)
+    }
+});
+
+graph_class!(UndirectedGraph, Undirected, {
+    pub fn is_cyclic(&self) -> bool {
+        is_cyclic_undirected(&self.core.graph)
+    }
+
+    pub fn cycle_basis(&self) -> PyResult<PyDataFrame> {
+        let found = cycles::cycle_basis(&self.core.graph);
+        Ok(self.core.node_schema.expand(cycle_frame(
+            &self.core.graph,
+            &self.core.node_schema,
+            &found,
+        )?)?)
+    }
+
+    #[pyo3(signature = (nodes=None, weight=None))]
+    pub fn tsp_tour(
+        &self,
+        nodes: Option<PySeries>,
+        weight: Option<String>,
+    ) -> PyResult<PyDataFrame> {
+        let graph = reweighted(
+            &self.core.graph,
+            &self.core.edge_attributes,
+            weight.as_deref(),
+        )?;
+        let nodes = match nodes {
+            Some(series) => lookup_nodes(&self.core.node_indices, &series.0)?,
+            None => graph.node_indices().collect(),
+        };
+        let tour = approximation::tsp_tour(&graph, &nodes)?;
+        Ok(self
+            .core
+            .node_schema
+            .expand(walk_frame(&graph, &self.core.node_schema, &tour)?)?)
+    }
+
+    #[pyo3(signature = (terminals, weight=None))]
+    pub fn steiner_tree(
+        &self,
+        terminals: PySeries,
+        weight: Option<String>,
+    ) -> PyResult<PyDataFrame> {
+        let graph = reweighted(
+            &self.core.graph,
+            &self.core.edge_attributes,
+            weight.as_deref(),
+        )?;
+        let terminals = lookup_nodes(&self.core.node_indices, &terminals.0)?;
+        let tree = approximation::steiner_tree(&graph, &terminals)?;
+        Ok(self
+            .core
+            .node_schema
+            .expand(walk_frame(&graph, &self.core.node_schema, &tree)?)?)
+    }
+
+    #[pyo3(signature = (min_size=1, limit=None))]
+    pub fn find_cliques(&self, min_size: usize, limit: Option<usize>) -> PyResult<PyDataFrame> {
+        let found = cliques::maximal_cliques(&self.core.graph, min_size, limit);
+        let ids: Vec<u32> = (0..found.len() as u32).collect();
+        let df = DataFrame::new(vec![
+            Series::new("clique_id".into(), ids).into(),
+            node_list_series("members", &self.core.graph, &self.core.node_schema, &found)?.into(),
+        ])
+        .map_err(GraphError::from)?;
+        Ok(self.core.node_schema.expand(df)?)
+    }
+
+    pub fn max_clique(&self) -> PyResult<NodeColumn> {
+        let members = cliques::maximum_clique(&self.core.graph);
+        Ok(self.core.node_schema.expand_series(node_series(
+            "node",
+            &self.core.graph,
+            &self.core.node_schema,
+            &members,
+        )?)?)
+    }
+
+    #[pyo3(signature = (weight=None))]
+    pub fn minimum_cut(&self, weight: Option<String>) -> PyResult<(f64, NodeColumn, NodeColumn)> {
+        let graph = reweighted(
+            &self.core.graph,
+            &self.core.edge_attributes,
+            weight.as_deref(),
+        )?;
+        let (value, side) = cut::minimum_cut(&graph)?;
+        let rest: Vec<NodeIndex> = self
+            .core
+            .graph
+            .node_indices()
+            .filter(|n| side.binary_search(n).is_err())
+            .collect();
+        Ok((
+            value,
+            self.core.node_schema.expand_series(node_series(
+                "node",
+                &graph,
+                &self.core.node_schema,
+                &side,
+            )?)?,
+            self.core.node_schema.expand_series(node_series(
+                "node",
+                &graph,
+                &self.core.node_schema,
+                &rest,
+            )?)?,
+        ))
+    }
+
+    #[pyo3(signature = (pairs=None, left_column=String::from("left"), right_column=String::from("right")))]
+    pub fn link_prediction(
+        &self,
+        pairs: Option<PyDataFrame>,
+        left_column: String,
+        right_column: String,
+    ) -> PyResult<PyDataFrame> {
+        let predictor = LinkPredictor::new(&self.core.graph);
+        let columns: Vec<Column> = match pairs {
+            Some(pairs) => {
+                let pairs = pairs.0;
+                let left = pairs.column(&left_column).map_err(GraphError::from)?;
+                let right = pairs.column(&right_column).map_err(GraphError::from)?;
+                let scores: Vec<Option<PairScores>> = izip!(
+                    extract_optional_node_data(left.as_materialized_series())?,
+                    extract_optional_node_data(right.as_materialized_series())?
+                )
+                .map(|(a, b)| {
+                    let a = a.and_then(|a| self.core.node_indices.get(&a).copied())?;
+                    let b = b.and_then(|b| self.core.node_indices.get(&b).copied())?;
+                    Some(predictor.score(a, b))
+                })
+                .collect();
+                let mut columns = vec![left.clone(), right.clone()];
+                columns.extend(score_columns(&scores));
+                columns
+            }
+            None => {
+                let (left, right): (Vec<NodeIndex>, Vec<NodeIndex>) =
+                    predictor.two_hop_pairs().into_iter().unzip();
+                let scores: Vec<Option<PairScores>> = izip!(&left, &right)
+                    .map(|(&a, &b)| Some(predictor.score(a, b)))
+                    .collect();
+                let mut columns = vec![
+                    node_series(
+                        &left_column,
+                        &self.core.graph,
+                        &self.core.node_schema,
+                        &left,
+                    )?
+                    .into(),
+                    node_series(
+                        &right_column,
+                        &self.core.graph,
+                        &self.core.node_schema,
+                        &right,
+                    )?
+                    .into(),
+                ];
+                columns.extend(score_columns(&scores));
+                columns
+            }
+        };
+        Ok(self
+            .core
+            .node_schema
+            .expand(DataFrame::new(columns).map_err(GraphError::from)?)?)
+    }
+});
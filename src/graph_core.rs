@@ -0,0 +1,266 @@
+use crate::attributes::{fold_rows, AttributeRows, EdgeAttributes};
+use crate::graph::{
+    extract_optional_node_data, lookup_node, GraphError, NodeArg, NodeData, NodeSchema,
+};
+use crate::ingest::{EdgeReader, EdgeRows, GraphOptions, KeyColumns, MergedRow, MultiEdges};
+use itertools::izip;
+use petgraph::graph::NodeIndex;
+use petgraph::stable_graph::{EdgeIndex, StableGraph};
+use petgraph::visit::EdgeRef;
+use petgraph::{Direction, EdgeType};
+use pyo3_polars::export::polars_core::prelude::*;
+use std::collections::HashMap;
+
+/// the state behind both graph classes and the plugin expressions: the petgraph graph,
+/// its node lookup and the attribute rows that travel with nodes and edges. every way
+/// of building or changing a graph goes through here so it behaves the same everywhere
+pub struct GraphCore<Ty: EdgeType> {
+    pub graph: StableGraph<NodeData, f64, Ty>,
+    pub node_indices: HashMap<NodeData, NodeIndex>,
+    pub node_schema: NodeSchema,
+    pub node_rows: AttributeRows,
+    pub edge_attributes: EdgeAttributes,
+    /// how the input was read, so deltas are read the same way
+    pub reader: EdgeReader,
+}
+
+impl<Ty: EdgeType> GraphCore<Ty> {
+    /// builds a graph from an edge frame, nodes of `options.nodes` come first so node
+    /// indices follow the node frame
+    pub fn build(
+        frame: &DataFrame,
+        sources_column: KeyColumns,
+        sinks_column: KeyColumns,
+        weights_column: Option<String>,
+        mut options: GraphOptions,
+    ) -> Result<Self, GraphError> {
+        let node_frame = options.nodes.take();
+        let reader = EdgeReader {
+            sources_column,
+            sinks_column,
+            weights_column,
+            options,
+            directed: Ty::is_directed(),
+        };
+        let rows = reader.edges(frame)?;
+        let mut core = GraphCore {
+            graph: StableGraph::default(),
+            node_indices: HashMap::new(),
+            node_schema: rows.schema.clone(),
+            node_rows: AttributeRows::default(),
+            edge_attributes: EdgeAttributes::new(reader.weights_column.clone()),
+            reader,
+        };
+        if let Some(node_frame) = &node_frame {
+            core.add_nodes(node_frame)?;
+        }
+        let attributes = core.reader.attributes(frame);
+        core.insert_rows(rows, &attributes)?;
+        Ok(core)
+    }
+
+    /// the index of a node named by a python argument
+    pub fn node_index(&self, node: NodeArg) -> Result<NodeIndex, GraphError> {
+        lookup_node(&self.node_indices, &self.node_schema.coerce(node)?)
+    }
+
+    /// the index of a node, added without attributes when the graph does not have it yet
+    fn intern_node(&mut self, node: NodeData) -> NodeIndex {
+        let GraphCore {
+            graph,
+            node_indices,
+            node_rows,
+            ..
+        } = self;
+        *node_indices.entry(node.clone()).or_insert_with(|| {
+            let index = graph.add_node(node);
+            // a removed node's index may be handed out again
+            node_rows.clear(index.index());
+            index
+        })
+    }
+
+    /// adds read edge rows whose attribute columns are `attributes`, merging them into
+    /// existing edges when the multi edge policy combines parallel edges
+    fn insert_rows(&mut self, rows: EdgeRows, attributes: &DataFrame) -> Result<(), GraphError> {
+        self.node_schema.check(&rows.schema.dtype, "edge key")?;
+        let policy = self.reader.options.multi_edges;
+        // the policy also merges a delta edge into an edge the graph already has
+        let targets: Vec<Option<EdgeIndex>> = rows
+            .edges
+            .iter()
+            .map(
+                |(src, dest, _)| match (self.node_indices.get(src), self.node_indices.get(dest)) {
+                    (Some(&a), Some(&b)) if policy != MultiEdges::Keep => {
+                        self.graph.find_edge(a, b)
+                    }
+                    _ => None,
+                },
+            )
+            .collect();
+        // everything that can fail comes before the graph changes
+        let attributes = fold_rows(attributes, policy, &rows.merged)?;
+        let staged = self.edge_attributes.stage(&attributes)?;
+        let merged: Vec<MergedRow> = izip!(&targets, &rows.rows, &rows.counts)
+            .filter_map(|(target, &row, &count)| {
+                let edge = (*target)?;
+                Some(MergedRow {
+                    kept: self.edge_attributes.row(edge)?,
+                    row: staged.row(row),
+                    kept_count: self.edge_attributes.count(edge),
+                    count,
+                })
+            })
+            .collect();
+        let staged = staged.fold(policy, &merged)?;
+        for node in rows.nodes {
+            self.intern_node(node);
+        }
+        let mut assigned = Vec::new();
+        for ((src, dest, wght), row, count, target) in
+            izip!(rows.edges, rows.rows, rows.counts, targets)
+        {
+            match target {
+                Some(edge) => {
+                    let merged = self.edge_attributes.count(edge);
+                    self.graph[edge] = policy.combine(self.graph[edge], merged, wght, count);
+                    self.edge_attributes.merge_into(edge, count);
+                }
+                None => {
+                    let (a, b) = (self.node_indices[&src], self.node_indices[&dest]);
+                    assigned.push((self.graph.add_edge(a, b, wght), row, count));
+                }
+            }
+        }
+        self.edge_attributes.append(staged, &assigned)
+    }
+
+    /// adds the edges of a delta frame read like the graph's input, creating the nodes it
+    /// mentions for the first time
+    pub fn add_edges(&mut self, frame: &DataFrame) -> Result<(), GraphError> {
+        let frame = self.reader.prepare(frame, true)?;
+        let rows = self.reader.edges(&frame)?;
+        let attributes = self.reader.attributes(&frame);
+        self.insert_rows(rows, &attributes)
+    }
+
+    /// adds the nodes of a node frame, nodes already in the graph take its attributes
+    pub fn add_nodes(&mut self, frame: &DataFrame) -> Result<(), GraphError> {
+        let declared = self.reader.nodes(frame, &self.node_schema)?;
+        let staged = self.node_rows.stage(&declared.attributes)?;
+        let assigned: Vec<(usize, IdxSize)> = declared
+            .nodes
+            .into_iter()
+            .enumerate()
+            .map(|(row, node)| (self.intern_node(node).index(), row as IdxSize))
+            .collect();
+        self.node_rows.append(staged, assigned)
+    }
+
+    /// removes every edge between the source and sink of each row, returning how many went,
+    /// rows naming unknown nodes or null keys remove nothing
+    pub fn remove_edges(&mut self, frame: &DataFrame) -> Result<usize, GraphError> {
+        let frame = self.reader.prepare(frame, false)?;
+        let mut removed = 0;
+        for (source, sink) in self
+            .reader
+            .endpoints(&frame, &self.node_schema)?
+            .into_iter()
+            .flatten()
+        {
+            let (Some(&a), Some(&b)) =
+                (self.node_indices.get(&source), self.node_indices.get(&sink))
+            else {
+                continue;
+            };
+            let edges: Vec<EdgeIndex> = self.graph.edges_connecting(a, b).map(|e| e.id()).collect();
+            for edge in edges {
+                if self.graph.remove_edge(edge).is_some() {
+                    self.edge_attributes.clear(edge);
+                    removed += 1;
+                }
+            }
+        }
+        self.edge_attributes.compact()?;
+        Ok(removed)
+    }
+
+    /// removes the given nodes with their edges, returning how many were in the graph
+    pub fn remove_nodes(&mut self, nodes: &Series) -> Result<usize, GraphError> {
+        self.node_schema.check(nodes.dtype(), "node")?;
+        let mut removed = 0;
+        for node in extract_optional_node_data(nodes)?.into_iter().flatten() {
+            if let Some(index) = self.node_indices.remove(&node) {
+                let edges: Vec<EdgeIndex> = self
+                    .graph
+                    .edges_directed(index, Direction::Outgoing)
+                    .chain(self.graph.edges_directed(index, Direction::Incoming))
+                    .map(|e| e.id())
+                    .collect();
+                for edge in edges {
+                    self.edge_attributes.clear(edge);
+                }
+                self.graph.remove_node(index);
+                self.node_rows.clear(index.index());
+                removed += 1;
+            }
+        }
+        self.edge_attributes.compact()?;
+        self.node_rows.compact()?;
+        Ok(removed)
+    }
+
+    /// sets the weight of every edge between the source and sink of each row, read from
+    /// `weights_column` or the graph's own. the whole frame is refused when a row names an
+    /// edge the graph does not have, and so is every update when the multi edge policy
+    /// aggregates, as one weight cannot stand in for the rows an edge merged
+    pub fn update_weights(
+        &mut self,
+        frame: &DataFrame,
+        weights_column: Option<String>,
+    ) -> Result<(), GraphError> {
+        let policy = self.reader.options.multi_edges;
+        if !matches!(policy, MultiEdges::Keep | MultiEdges::First) {
+            return Err(GraphError::InvalidArgument(format!(
+                "weights cannot be updated when multi_edges is {}",
+                format!("{:?}", policy).to_lowercase()
+            )));
+        }
+        // a column given here is read as is, the graph's own may be computed
+        let frame = self.reader.prepare(frame, weights_column.is_none())?;
+        let weights_column = weights_column.or_else(|| self.reader.weights_column.clone());
+        if weights_column.is_none() {
+            return Err(GraphError::InvalidArgument(
+                "the graph has no weights column, pass the column to read".into(),
+            ));
+        }
+        let reader = EdgeReader {
+            weights_column,
+            ..self.reader.clone()
+        };
+        let rows = reader.read(&frame)?;
+        self.node_schema.check(&rows.schema.dtype, "edge key")?;
+        let mut updates = Vec::with_capacity(rows.edges.len());
+        let mut missing = Vec::new();
+        for ((src, dest, wght), row) in rows.edges.iter().zip(&rows.rows) {
+            let edges: Vec<EdgeIndex> =
+                match (self.node_indices.get(src), self.node_indices.get(dest)) {
+                    (Some(&a), Some(&b)) => {
+                        self.graph.edges_connecting(a, b).map(|e| e.id()).collect()
+                    }
+                    _ => Vec::new(),
+                };
+            if edges.is_empty() {
+                missing.push(*row as usize);
+            }
+            updates.extend(edges.into_iter().map(|edge| (edge, *wght)));
+        }
+        if !missing.is_empty() {
+            return Err(GraphError::InvalidRows("no such edge".into(), missing));
+        }
+        for (edge, wght) in updates {
+            self.graph[edge] = wght;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,754 @@
+use crate::graph::{extract_optional_node_data, node_key_dtype, GraphError, NodeData, NodeSchema};
+use polars::prelude::{col, Expr, IntoLazy};
+use pyo3::FromPyObject;
+use pyo3_polars::export::polars_core::prelude::*;
+use pyo3_polars::export::polars_plan::utils::expr_output_name;
+use pyo3_polars::{PyDataFrame, PyExpr, PyLazyFrame};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// the column, or columns for a composite key, identifying a node
+#[derive(Clone, Debug, FromPyObject)]
+pub enum KeyColumns {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<&str> for KeyColumns {
+    fn from(column: &str) -> Self {
+        KeyColumns::One(column.to_string())
+    }
+}
+
+impl From<String> for KeyColumns {
+    fn from(column: String) -> Self {
+        KeyColumns::One(column)
+    }
+}
+
+impl KeyColumns {
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            KeyColumns::One(column) => vec![column.as_str()],
+            KeyColumns::Many(columns) => columns.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// the key as a single series, several columns become one struct series whose
+    /// fields are called `field_names`
+    pub fn read(&self, frame: &DataFrame, field_names: &[&str]) -> Result<Series, GraphError> {
+        match self.names().as_slice() {
+            [] => Err(GraphError::InvalidArgument(
+                "a node key needs at least one column".into(),
+            )),
+            [column] => Ok(frame.column(column)?.as_materialized_series().clone()),
+            columns => {
+                if columns.len() != field_names.len() {
+                    return Err(GraphError::InvalidArgument(format!(
+                        "composite keys must have {} columns, got {}",
+                        field_names.len(),
+                        columns.len()
+                    )));
+                }
+                let fields = columns
+                    .iter()
+                    .zip(field_names)
+                    .map(|(column, name)| {
+                        Ok(frame
+                            .column(column)?
+                            .as_materialized_series()
+                            .clone()
+                            .with_name((*name).into()))
+                    })
+                    .collect::<Result<Vec<Series>, GraphError>>()?;
+                Ok(
+                    StructChunked::from_series("key".into(), frame.height(), fields.iter())?
+                        .into_series(),
+                )
+            }
+        }
+    }
+}
+
+/// how rows with a null source, sink or weight are treated while building a graph
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NullPolicy {
+    /// refuse the input and report the offending rows
+    #[default]
+    Error,
+    /// skip the whole row
+    DropRow,
+    /// skip the edge but keep whichever endpoints are present as nodes
+    IsolatedNode,
+}
+
+impl TryFrom<&str> for NullPolicy {
+    type Error = GraphError;
+
+    fn try_from(value: &str) -> Result<Self, GraphError> {
+        match value {
+            "error" => Ok(NullPolicy::Error),
+            "drop_row" => Ok(NullPolicy::DropRow),
+            "isolated_node" => Ok(NullPolicy::IsolatedNode),
+            other => Err(GraphError::InvalidArgument(format!(
+                "null_policy must be one of error, drop_row or isolated_node, got {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// how rows naming the same edge are combined
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MultiEdges {
+    /// every row becomes its own parallel edge
+    #[default]
+    Keep,
+    /// the first row wins
+    First,
+    Sum,
+    Min,
+    Max,
+    Mean,
+    /// one edge weighted by the number of rows
+    Count,
+}
+
+impl TryFrom<&str> for MultiEdges {
+    type Error = GraphError;
+
+    fn try_from(value: &str) -> Result<Self, GraphError> {
+        match value {
+            "keep" => Ok(MultiEdges::Keep),
+            "first" => Ok(MultiEdges::First),
+            "sum" => Ok(MultiEdges::Sum),
+            "min" => Ok(MultiEdges::Min),
+            "max" => Ok(MultiEdges::Max),
+            "mean" => Ok(MultiEdges::Mean),
+            "count" => Ok(MultiEdges::Count),
+            other => Err(GraphError::InvalidArgument(format!(
+                "multi_edges must be one of keep, first, sum, min, max, mean or count, got {}",
+                other
+            ))),
+        }
+    }
+}
+
+impl MultiEdges {
+    /// the weight a single row gives its edge
+    pub fn initial(self, weight: f64) -> f64 {
+        match self {
+            MultiEdges::Count => 1.0,
+            _ => weight,
+        }
+    }
+
+    /// the weight of an edge merged from `count` rows once an edge merged from `more` rows
+    /// joins it
+    pub fn combine(self, weight: f64, count: u32, other: f64, more: u32) -> f64 {
+        match self {
+            MultiEdges::Keep | MultiEdges::First => weight,
+            MultiEdges::Sum | MultiEdges::Count => weight + other,
+            MultiEdges::Min => weight.min(other),
+            MultiEdges::Max => weight.max(other),
+            MultiEdges::Mean => {
+                (weight * count as f64 + other * more as f64) / (count + more) as f64
+            }
+        }
+    }
+}
+
+/// how rows whose source is their sink are treated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelfLoops {
+    #[default]
+    Keep,
+    /// skip the edge but keep its node
+    Drop,
+    /// refuse the input and report the offending rows
+    Error,
+}
+
+impl TryFrom<&str> for SelfLoops {
+    type Error = GraphError;
+
+    fn try_from(value: &str) -> Result<Self, GraphError> {
+        match value {
+            "keep" => Ok(SelfLoops::Keep),
+            "drop" => Ok(SelfLoops::Drop),
+            "error" => Ok(SelfLoops::Error),
+            other => Err(GraphError::InvalidArgument(format!(
+                "self_loops must be one of keep, drop or error, got {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// weight values refused while building a graph, all accepted by default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WeightChecks {
+    pub nan: bool,
+    pub infinite: bool,
+    pub negative: bool,
+}
+
+impl TryFrom<&[String]> for WeightChecks {
+    type Error = GraphError;
+
+    fn try_from(names: &[String]) -> Result<Self, GraphError> {
+        let mut checks = WeightChecks::default();
+        for name in names {
+            match name.as_str() {
+                "nan" => checks.nan = true,
+                "infinite" => checks.infinite = true,
+                "negative" => checks.negative = true,
+                other => {
+                    return Err(GraphError::InvalidArgument(format!(
+                        "validate_weights entries must be nan, infinite or negative, got {}",
+                        other
+                    )))
+                }
+            }
+        }
+        Ok(checks)
+    }
+}
+
+impl WeightChecks {
+    /// rows whose weight fails the first enabled check, with a description of the check
+    fn failures(&self, weights: &[Option<f64>], rows: &[usize]) -> Option<(String, Vec<usize>)> {
+        let checks = [
+            (self.nan, "NaN weight", f64::is_nan as fn(f64) -> bool),
+            (self.infinite, "infinite weight", f64::is_infinite),
+            (self.negative, "negative weight", |w| w < 0.0),
+        ];
+        checks
+            .into_iter()
+            .filter(|(enabled, _, _)| *enabled)
+            .find_map(|(_, name, fails)| {
+                let bad: Vec<usize> = rows
+                    .iter()
+                    .copied()
+                    .filter(|&row| weights[row].is_some_and(fails))
+                    .collect();
+                (!bad.is_empty()).then(|| (name.to_string(), bad))
+            })
+    }
+}
+
+/// construction options shared by both graph classes
+#[derive(Clone, Debug, Default)]
+pub struct GraphOptions {
+    pub null_policy: NullPolicy,
+    /// weight given to rows whose weight is null, before the null policy applies
+    pub default_weight: Option<f64>,
+    pub weight_checks: WeightChecks,
+    /// how rows naming the same edge merge, numeric attribute columns along with the weights
+    pub multi_edges: MultiEdges,
+    pub self_loops: SelfLoops,
+    /// node rows, possibly with nodes no edge mentions, whose other columns become attributes
+    pub nodes: Option<DataFrame>,
+    /// key columns of `nodes`, the sources columns when not given
+    pub node_key: Option<KeyColumns>,
+    /// expressions the key and weight columns were computed with, re-applied to deltas
+    pub expressions: Option<EdgeExprs>,
+}
+
+impl GraphOptions {
+    /// options from the constructor arguments as python passes them
+    pub fn from_py(
+        null_policy: &str,
+        nodes: Option<FrameInput>,
+        node_key: Option<KeyColumns>,
+        default_weight: Option<f64>,
+        validate_weights: &[String],
+        multi_edges: &str,
+        self_loops: &str,
+    ) -> Result<Self, GraphError> {
+        Ok(GraphOptions {
+            null_policy: NullPolicy::try_from(null_policy)?,
+            default_weight,
+            weight_checks: WeightChecks::try_from(validate_weights)?,
+            multi_edges: MultiEdges::try_from(multi_edges)?,
+            self_loops: SelfLoops::try_from(self_loops)?,
+            nodes: nodes.map(FrameInput::collect).transpose()?,
+            node_key,
+            expressions: None,
+        })
+    }
+}
+
+/// edges read row by row from an input frame
+#[derive(Debug, Default)]
+pub struct EdgeRows {
+    pub schema: NodeSchema,
+    /// distinct nodes in order of first appearance, sources before sinks
+    pub nodes: Vec<NodeData>,
+    pub edges: Vec<(NodeData, NodeData, f64)>,
+    /// the input row every edge came from, the first one for merged edges
+    pub rows: Vec<IdxSize>,
+    /// how many input rows every edge merges
+    pub counts: Vec<u32>,
+    /// input rows merged into the edge of an earlier row, in input order
+    pub merged: Vec<MergedRow>,
+}
+
+/// an input row merged into the edge of an earlier row
+#[derive(Clone, Copy, Debug)]
+pub struct MergedRow {
+    /// the row the edge keeps its attributes in
+    pub kept: IdxSize,
+    pub row: IdxSize,
+    /// input rows the edge merged before this one
+    pub kept_count: u32,
+    /// input rows this row stands for
+    pub count: u32,
+}
+
+impl EdgeRows {
+    /// combines edges sharing their endpoints, in either order for undirected graphs
+    pub fn merge_parallel(&mut self, policy: MultiEdges, directed: bool) {
+        if policy == MultiEdges::Keep {
+            return;
+        }
+        let mut merged = EdgeRows {
+            schema: self.schema.clone(),
+            nodes: std::mem::take(&mut self.nodes),
+            ..EdgeRows::default()
+        };
+        let mut positions: HashMap<(NodeData, NodeData), usize> = HashMap::new();
+        let edges = std::mem::take(&mut self.edges);
+        for ((src, dest, wght), (row, count)) in
+            edges.into_iter().zip(self.rows.iter().zip(&self.counts))
+        {
+            let wght = policy.initial(wght);
+            let known = positions.get(&(src.clone(), dest.clone())).or_else(|| {
+                (!directed)
+                    .then(|| positions.get(&(dest.clone(), src.clone())))
+                    .flatten()
+            });
+            match known {
+                Some(&at) => {
+                    merged.merged.push(MergedRow {
+                        kept: merged.rows[at],
+                        row: *row,
+                        kept_count: merged.counts[at],
+                        count: *count,
+                    });
+                    let weight = &mut merged.edges[at].2;
+                    *weight = policy.combine(*weight, merged.counts[at], wght, *count);
+                    merged.counts[at] += count;
+                }
+                None => {
+                    positions.insert((src.clone(), dest.clone()), merged.edges.len());
+                    merged.edges.push((src, dest, wght));
+                    merged.rows.push(*row);
+                    merged.counts.push(*count);
+                }
+            }
+        }
+        *self = merged;
+    }
+}
+
+/// reads sources, sinks and weights together so a null in one column never shifts the others
+pub fn read_edge_rows(
+    frame: &DataFrame,
+    sources_column: &KeyColumns,
+    sinks_column: &KeyColumns,
+    weights_column: Option<&str>,
+    options: &GraphOptions,
+) -> Result<EdgeRows, GraphError> {
+    let null_policy = options.null_policy;
+    // sinks take the source column names so both sides describe the same composite key
+    let field_names = sources_column.names();
+    let sources = sources_column.read(frame, &field_names)?;
+    let sinks = sinks_column.read(frame, &field_names)?;
+    if node_key_dtype(sources.dtype())? != node_key_dtype(sinks.dtype())? {
+        return Err(GraphError::InvalidDataType(format!(
+            "source dtype {} does not match sink dtype {}",
+            sources.dtype(),
+            sinks.dtype()
+        )));
+    }
+    let schema = NodeSchema::new(sources.dtype())?;
+    let sources = extract_optional_node_data(&sources)?;
+    let sinks = extract_optional_node_data(&sinks)?;
+    let weights = match weights_column {
+        Some(column) => read_weights(frame, column, options.default_weight)?,
+        None => vec![Some(1.0); frame.height()],
+    };
+
+    let complete =
+        |row: usize| sources[row].is_some() && sinks[row].is_some() && weights[row].is_some();
+    if null_policy == NullPolicy::Error {
+        let bad: Vec<usize> = (0..frame.height()).filter(|&row| !complete(row)).collect();
+        if !bad.is_empty() {
+            return Err(GraphError::InvalidRows(
+                "null source, sink or weight".into(),
+                bad,
+            ));
+        }
+    }
+
+    let mut kept: Vec<usize> = (0..frame.height()).filter(|&row| complete(row)).collect();
+    if let Some((reason, bad)) = options.weight_checks.failures(&weights, &kept) {
+        return Err(GraphError::InvalidRows(reason, bad));
+    }
+    let is_loop = |row: &usize| sources[*row] == sinks[*row];
+    match options.self_loops {
+        SelfLoops::Keep => {}
+        SelfLoops::Drop => kept.retain(|row| !is_loop(row)),
+        SelfLoops::Error => {
+            let bad: Vec<usize> = kept.iter().copied().filter(is_loop).collect();
+            if !bad.is_empty() {
+                return Err(GraphError::InvalidRows("self-loop".into(), bad));
+            }
+        }
+    }
+
+    let mut rows = EdgeRows {
+        schema,
+        ..EdgeRows::default()
+    };
+    let mut seen = HashSet::new();
+    for column in [&sources, &sinks] {
+        for (row, node) in column.iter().enumerate() {
+            let keep = complete(row) || null_policy == NullPolicy::IsolatedNode;
+            if let Some(node) = node.as_ref().filter(|_| keep) {
+                if seen.insert(node) {
+                    rows.nodes.push(node.clone());
+                }
+            }
+        }
+    }
+    for row in kept {
+        rows.edges.push((
+            sources[row].clone().expect("complete row"),
+            sinks[row].clone().expect("complete row"),
+            weights[row].expect("complete row"),
+        ));
+        rows.rows.push(row as IdxSize);
+        rows.counts.push(1);
+    }
+    Ok(rows)
+}
+
+/// weights of any numeric dtype cast to f64, nulls replaced by `default` when given
+fn read_weights(
+    frame: &DataFrame,
+    column: &str,
+    default: Option<f64>,
+) -> Result<Vec<Option<f64>>, GraphError> {
+    let weights = frame.column(column)?.as_materialized_series();
+    if !(weights.dtype().is_numeric() || weights.dtype() == &DataType::Null) {
+        return Err(GraphError::InvalidDataType(format!(
+            "weights column {} must be numeric, got {}",
+            column,
+            weights.dtype()
+        )));
+    }
+    let weights = weights.cast(&DataType::Float64)?;
+    Ok(weights
+        .f64()?
+        .into_iter()
+        .map(|weight| weight.or(default))
+        .collect())
+}
+
+/// nodes declared by a node frame
+#[derive(Debug, Default)]
+pub struct NodeRows {
+    pub nodes: Vec<NodeData>,
+    /// the node frame without its key columns, row i describes `nodes[i]`
+    pub attributes: DataFrame,
+}
+
+/// reads a node frame whose keys must match the edge key dtype, rejecting null and
+/// duplicate keys
+pub fn read_node_rows(
+    frame: &DataFrame,
+    key: &KeyColumns,
+    field_names: &[&str],
+    schema: &NodeSchema,
+) -> Result<NodeRows, GraphError> {
+    let keys = key.read(frame, field_names)?;
+    schema.check(keys.dtype(), "node key")?;
+    let keys = extract_optional_node_data(&keys)?;
+    let missing: Vec<usize> = (0..keys.len()).filter(|&row| keys[row].is_none()).collect();
+    if !missing.is_empty() {
+        return Err(GraphError::InvalidRows("null node key".into(), missing));
+    }
+    let mut seen = HashSet::new();
+    let repeated: Vec<usize> = (0..keys.len())
+        .filter(|&row| !seen.insert(&keys[row]))
+        .collect();
+    if !repeated.is_empty() {
+        return Err(GraphError::InvalidRows(
+            "duplicate node key".into(),
+            repeated,
+        ));
+    }
+    Ok(NodeRows {
+        nodes: keys.into_iter().flatten().collect(),
+        attributes: frame.drop_many(key.names()),
+    })
+}
+
+/// how a graph reads its input frames, kept so later deltas are read like the frames it
+/// was built from
+#[derive(Clone, Debug)]
+pub struct EdgeReader {
+    pub sources_column: KeyColumns,
+    pub sinks_column: KeyColumns,
+    pub weights_column: Option<String>,
+    pub options: GraphOptions,
+    /// whether parallel edges must also agree on direction to be merged
+    pub directed: bool,
+}
+
+impl EdgeReader {
+    /// a delta frame with the key columns, and the weight column when `weights`, computed
+    /// by the expressions the graph's input was read with
+    pub fn prepare<'a>(
+        &self,
+        frame: &'a DataFrame,
+        weights: bool,
+    ) -> Result<Cow<'a, DataFrame>, GraphError> {
+        match &self.options.expressions {
+            Some(expressions) => Ok(Cow::Owned(expressions.apply(frame, weights)?)),
+            None => Ok(Cow::Borrowed(frame)),
+        }
+    }
+
+    /// edge rows exactly as the frame gives them
+    pub fn read(&self, frame: &DataFrame) -> Result<EdgeRows, GraphError> {
+        read_edge_rows(
+            frame,
+            &self.sources_column,
+            &self.sinks_column,
+            self.weights_column.as_deref(),
+            &self.options,
+        )
+    }
+
+    /// edge rows with parallel edges merged by the multi edge policy
+    pub fn edges(&self, frame: &DataFrame) -> Result<EdgeRows, GraphError> {
+        let mut rows = self.read(frame)?;
+        rows.merge_parallel(self.options.multi_edges, self.directed);
+        Ok(rows)
+    }
+
+    /// node rows keyed by `node_key`, or by the sources columns when it was not given
+    pub fn nodes(&self, frame: &DataFrame, schema: &NodeSchema) -> Result<NodeRows, GraphError> {
+        read_node_rows(
+            frame,
+            self.options
+                .node_key
+                .as_ref()
+                .unwrap_or(&self.sources_column),
+            &self.sources_column.names(),
+            schema,
+        )
+    }
+
+    /// the columns of an edge frame that are not node keys
+    pub fn attributes(&self, frame: &DataFrame) -> DataFrame {
+        let mut keys = self.sources_column.names();
+        keys.extend(self.sinks_column.names());
+        frame.drop_many(keys)
+    }
+
+    /// source and sink of every row of a frame that only names edges, null where a key is
+    pub fn endpoints(
+        &self,
+        frame: &DataFrame,
+        schema: &NodeSchema,
+    ) -> Result<Vec<Option<(NodeData, NodeData)>>, GraphError> {
+        let field_names = self.sources_column.names();
+        let sources = self.sources_column.read(frame, &field_names)?;
+        let sinks = self.sinks_column.read(frame, &field_names)?;
+        schema.check(sources.dtype(), "source")?;
+        schema.check(sinks.dtype(), "sink")?;
+        Ok(extract_optional_node_data(&sources)?
+            .into_iter()
+            .zip(extract_optional_node_data(&sinks)?)
+            .map(|(source, sink)| source.zip(sink))
+            .collect())
+    }
+}
+
+/// a constructor input frame, lazy frames are collected once the needed columns are known
+#[derive(FromPyObject)]
+#[allow(clippy::large_enum_variant)]
+pub enum FrameInput {
+    Eager(PyDataFrame),
+    Lazy(PyLazyFrame),
+}
+
+impl FrameInput {
+    /// the whole frame, collecting a lazy one
+    pub fn collect(self) -> Result<DataFrame, GraphError> {
+        match self {
+            FrameInput::Eager(df) => Ok(df.0),
+            FrameInput::Lazy(lf) => Ok(lf.0.collect()?),
+        }
+    }
+}
+
+/// a node key given by column names or by expressions over the input
+#[derive(Clone, FromPyObject)]
+pub enum KeySpec {
+    Columns(KeyColumns),
+    Expr(PyExpr),
+    Exprs(Vec<PyExpr>),
+}
+
+impl From<KeyColumns> for KeySpec {
+    fn from(columns: KeyColumns) -> Self {
+        KeySpec::Columns(columns)
+    }
+}
+
+impl KeySpec {
+    fn exprs(&self) -> Vec<Expr> {
+        match self {
+            KeySpec::Columns(columns) => columns.names().into_iter().map(col).collect(),
+            KeySpec::Expr(expr) => vec![expr.0.clone()],
+            KeySpec::Exprs(exprs) => exprs.iter().map(|e| e.0.clone()).collect(),
+        }
+    }
+}
+
+/// a weight given by column name or by an expression over the input
+#[derive(Clone, FromPyObject)]
+pub enum WeightSpec {
+    Column(String),
+    Expr(PyExpr),
+}
+
+impl WeightSpec {
+    fn expr(&self) -> Expr {
+        match self {
+            WeightSpec::Column(column) => col(column.as_str()),
+            WeightSpec::Expr(expr) => expr.0.clone(),
+        }
+    }
+}
+
+/// key and weight expressions over an edge frame, each named after the column it fills
+#[derive(Clone, Debug)]
+pub struct EdgeExprs {
+    pub keys: Vec<Expr>,
+    pub weights: Option<Expr>,
+}
+
+impl EdgeExprs {
+    /// the frame with its key columns, and its weight column when `weights`, replaced by
+    /// the values of the expressions
+    pub fn apply(&self, frame: &DataFrame, weights: bool) -> Result<DataFrame, GraphError> {
+        let mut exprs = self.keys.clone();
+        if weights {
+            exprs.extend(self.weights.clone());
+        }
+        Ok(frame.clone().lazy().with_columns(exprs).collect()?)
+    }
+}
+
+/// an edge frame holding plain key and weight columns
+pub struct EdgeInput {
+    pub frame: DataFrame,
+    pub sources_column: KeyColumns,
+    pub sinks_column: KeyColumns,
+    pub weights_column: Option<String>,
+    /// the expressions the columns were computed with, none when all were plain columns
+    pub expressions: Option<EdgeExprs>,
+}
+
+fn output_names(exprs: &[Expr]) -> Result<Vec<String>, GraphError> {
+    exprs
+        .iter()
+        .map(|e| Ok(expr_output_name(e)?.to_string()))
+        .collect()
+}
+
+fn key_columns(mut names: Vec<String>) -> KeyColumns {
+    match names.len() {
+        1 => KeyColumns::One(names.remove(0)),
+        _ => KeyColumns::Many(names),
+    }
+}
+
+/// evaluates key and weight expressions and selects them with the attribute columns, named
+/// after the expressions' output names. `attributes` defaults to every other column of an
+/// eager frame and to none for a lazy one, so a lazy frame only collects what the graph needs
+pub fn collect_edge_input(
+    input: FrameInput,
+    sources: KeySpec,
+    sinks: KeySpec,
+    weights: Option<WeightSpec>,
+    attributes: Option<Vec<String>>,
+) -> Result<EdgeInput, GraphError> {
+    let (source_exprs, sink_exprs) = (sources.exprs(), sinks.exprs());
+    let weight_expr = weights.as_ref().map(WeightSpec::expr);
+    let source_names = output_names(&source_exprs)?;
+    let sink_names = output_names(&sink_exprs)?;
+    let weight_name = output_names(weight_expr.as_slice())?.pop();
+
+    let plain = matches!(
+        (&sources, &sinks, &weights),
+        (
+            KeySpec::Columns(_),
+            KeySpec::Columns(_),
+            None | Some(WeightSpec::Column(_))
+        )
+    );
+    let (lazy, columns) = match input {
+        FrameInput::Eager(df) if plain && attributes.is_none() => {
+            return Ok(EdgeInput {
+                frame: df.0,
+                sources_column: key_columns(source_names),
+                sinks_column: key_columns(sink_names),
+                weights_column: weight_name,
+                expressions: None,
+            })
+        }
+        FrameInput::Eager(df) => {
+            let columns = attributes.unwrap_or_else(|| {
+                df.0.get_column_names()
+                    .into_iter()
+                    .map(|c| c.to_string())
+                    .collect()
+            });
+            (df.0.lazy(), columns)
+        }
+        FrameInput::Lazy(lf) => (lf.0, attributes.unwrap_or_default()),
+    };
+
+    let mut selected = source_names.clone();
+    selected.extend(sink_names.iter().cloned());
+    selected.extend(weight_name.iter().cloned());
+    let mut keys = source_exprs;
+    keys.extend(sink_exprs);
+    let expressions = (!plain).then(|| EdgeExprs {
+        keys: keys.clone(),
+        weights: weight_expr.clone(),
+    });
+    let mut exprs = keys;
+    exprs.extend(weight_expr);
+    // a key expression replaces the column it is named after
+    exprs.extend(
+        columns
+            .iter()
+            .filter(|c| !selected.contains(c))
+            .map(|c| col(c.as_str())),
+    );
+    Ok(EdgeInput {
+        frame: lazy.select(exprs).collect()?,
+        sources_column: key_columns(source_names),
+        sinks_column: key_columns(sink_names),
+        weights_column: weight_name,
+        expressions,
+    })
+}
@@ -1,5 +1,9 @@
+pub mod algorithms;
+pub mod attributes;
 pub mod expressions;
 pub mod graph;
+pub mod graph_core;
+pub mod ingest;
 pub mod pg_utils;
 
 use pyo3::types::{PyModule, PyModuleMethods};
@@ -0,0 +1,30 @@
+//! fixtures shared by the integration tests, every test file includes them with `mod common;`
+//! and uses the ones it needs
+#![allow(dead_code)]
+
+use polars::prelude::*;
+use polars_graph::graph::{DirectedGraph, NodeData, UndirectedGraph};
+use pyo3_polars::PyDataFrame;
+
+/// an unweighted edge frame with source and sink columns
+pub fn edges(sources: &[&str], sinks: &[&str]) -> PyDataFrame {
+    PyDataFrame(df!("source" => sources, "sink" => sinks).unwrap())
+}
+
+/// an edge frame with source, sink and weight columns
+pub fn weighted_edges(sources: &[&str], sinks: &[&str], weights: &[f64]) -> PyDataFrame {
+    PyDataFrame(df!("source" => sources, "sink" => sinks, "weight" => weights).unwrap())
+}
+
+pub fn directed(sources: &[&str], sinks: &[&str]) -> DirectedGraph {
+    DirectedGraph::new(edges(sources, sinks), "source".into(), "sink".into(), None).unwrap()
+}
+
+pub fn undirected(sources: &[&str], sinks: &[&str]) -> UndirectedGraph {
+    UndirectedGraph::new(edges(sources, sinks), "source".into(), "sink".into(), None).unwrap()
+}
+
+/// a node of a string keyed graph
+pub fn node(name: &str) -> NodeData {
+    NodeData::String(name.to_string())
+}
@@ -0,0 +1,136 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{directed, node};
+    use polars::prelude::*;
+    use polars_graph::graph::DirectedGraph;
+    use pyo3_polars::PyDataFrame;
+    use std::collections::HashMap;
+
+    /// CEO -> A -> C, A -> D -> F, CEO -> B -> E
+    fn org_chart() -> DirectedGraph {
+        directed(
+            &["CEO", "CEO", "A", "A", "B", "D"],
+            &["A", "B", "C", "D", "E", "F"],
+        )
+    }
+
+    fn depths(df: &DataFrame) -> HashMap<String, u32> {
+        let nodes = df.column("node").unwrap().str().unwrap().clone();
+        let depths = df.column("depth").unwrap().u32().unwrap().clone();
+        nodes
+            .into_no_null_iter()
+            .zip(depths.into_no_null_iter())
+            .map(|(n, d)| (n.to_string(), d))
+            .collect()
+    }
+
+    fn lca(
+        graph: &DirectedGraph,
+        left: &[Option<&str>],
+        right: &[Option<&str>],
+    ) -> Vec<Option<String>> {
+        let pairs = df!("left" => left, "right" => right).unwrap();
+        let result = graph
+            .lowest_common_ancestor(PyDataFrame(pairs), "left".into(), "right".into())
+            .unwrap()
+            .series()
+            .unwrap();
+        result
+            .str()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect()
+    }
+
+    mod traversal {
+        use super::*;
+
+        #[test]
+        fn ancestors_report_hop_depth() {
+            let df = org_chart().ancestors(node("F").into(), None).unwrap().0;
+            let found = depths(&df);
+            assert_eq!(found.len(), 3);
+            assert_eq!(found["D"], 1);
+            assert_eq!(found["A"], 2);
+            assert_eq!(found["CEO"], 3);
+        }
+
+        #[test]
+        fn descendants_respect_max_depth() {
+            let df = org_chart()
+                .descendants(node("CEO").into(), Some(1))
+                .unwrap()
+                .0;
+            let found = depths(&df);
+            assert_eq!(found.len(), 2);
+            assert!(found.contains_key("A") && found.contains_key("B"));
+
+            let all = org_chart().descendants(node("CEO").into(), None).unwrap().0;
+            assert_eq!(all.height(), 6);
+        }
+
+        #[test]
+        fn rejects_unknown_node() {
+            assert!(org_chart().ancestors(node("Z").into(), None).is_err());
+        }
+    }
+
+    mod lowest_common_ancestor {
+        use super::*;
+
+        #[test]
+        fn answers_tree_pairs_row_aligned() {
+            let result = lca(
+                &org_chart(),
+                &[Some("C"), Some("F"), Some("C"), Some("A"), None, Some("Z")],
+                &[
+                    Some("F"),
+                    Some("E"),
+                    Some("C"),
+                    Some("F"),
+                    Some("A"),
+                    Some("A"),
+                ],
+            );
+            assert_eq!(
+                result,
+                vec![
+                    Some("A".to_string()),
+                    Some("CEO".to_string()),
+                    Some("C".to_string()),
+                    Some("A".to_string()),
+                    None,
+                    None,
+                ]
+            );
+        }
+
+        #[test]
+        fn separate_trees_have_no_ancestor() {
+            let forest = directed(&["A", "X"], &["B", "Y"]);
+            assert_eq!(lca(&forest, &[Some("B")], &[Some("Y")]), vec![None]);
+        }
+
+        #[test]
+        fn picks_deepest_shared_ancestor_in_dag() {
+            let dag = directed(
+                &["X", "Y", "X", "Y", "R", "R", "Z"],
+                &["Z", "Z", "W", "W", "X", "Y", "Q"],
+            );
+            let result = lca(&dag, &[Some("Q"), Some("Z")], &[Some("W"), Some("R")]);
+            assert_eq!(result, vec![Some("X".to_string()), Some("R".to_string())]);
+        }
+
+        #[test]
+        fn rejects_cyclic_graph() {
+            let graph = directed(&["A", "B"], &["B", "A"]);
+            let pairs = df!("left" => ["A"], "right" => ["B"]).unwrap();
+            assert!(graph
+                .lowest_common_ancestor(PyDataFrame(pairs), "left".into(), "right".into())
+                .is_err());
+        }
+    }
+}
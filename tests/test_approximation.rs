@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::UndirectedGraph;
+    use pyo3_polars::{PyDataFrame, PySeries};
+    use std::collections::HashSet;
+
+    /// hub H reaches A, B and C cheaply while the direct A-B and B-C roads are long,
+    /// D hangs off C and E is only reachable through D
+    fn roads() -> UndirectedGraph {
+        let df = df!(
+            "source" => ["H", "H", "H", "A", "B", "C", "D"],
+            "sink" => ["A", "B", "C", "B", "C", "D", "E"],
+            "weight" => [1.0, 1.0, 1.0, 5.0, 5.0, 2.0, 2.0]
+        )
+        .unwrap();
+        UndirectedGraph::new(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap()
+    }
+
+    fn series(values: &[&str]) -> PySeries {
+        PySeries(Series::new("nodes".into(), values))
+    }
+
+    fn steps(df: &DataFrame) -> Vec<(String, String, f64)> {
+        let sources = df.column("source").unwrap().str().unwrap().clone();
+        let targets = df.column("target").unwrap().str().unwrap().clone();
+        let weights = df.column("weight").unwrap().f64().unwrap().clone();
+        sources
+            .into_no_null_iter()
+            .zip(targets.into_no_null_iter())
+            .zip(weights.into_no_null_iter())
+            .map(|((s, t), w)| (s.to_string(), t.to_string(), w))
+            .collect()
+    }
+
+    #[test]
+    fn tour_is_a_closed_walk_over_the_subset() {
+        let tour = steps(
+            &roads()
+                .tsp_tour(Some(series(&["A", "B", "C"])), None)
+                .unwrap()
+                .0,
+        );
+        assert_eq!(tour.first().unwrap().0, tour.last().unwrap().1);
+        for pair in tour.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+        let visited: HashSet<&str> = tour.iter().map(|s| s.1.as_str()).collect();
+        assert!(["A", "B", "C"].iter().all(|n| visited.contains(n)));
+        assert_eq!(tour.iter().map(|s| s.2).sum::<f64>(), 6.0);
+    }
+
+    #[test]
+    fn tour_over_whole_graph_visits_every_node() {
+        let tour = steps(&roads().tsp_tour(None, None).unwrap().0);
+        let visited: HashSet<&str> = tour.iter().map(|s| s.1.as_str()).collect();
+        assert_eq!(visited.len(), 6);
+    }
+
+    #[test]
+    fn single_stop_has_empty_tour() {
+        assert_eq!(
+            roads()
+                .tsp_tour(Some(series(&["A"])), None)
+                .unwrap()
+                .0
+                .height(),
+            0
+        );
+    }
+
+    #[test]
+    fn steiner_tree_routes_through_the_hub() {
+        let tree = steps(
+            &roads()
+                .steiner_tree(series(&["A", "B", "E"]), None)
+                .unwrap()
+                .0,
+        );
+        let edges: HashSet<(String, String)> = tree
+            .iter()
+            .map(|(s, t, _)| {
+                if s < t {
+                    (s.clone(), t.clone())
+                } else {
+                    (t.clone(), s.clone())
+                }
+            })
+            .collect();
+        let expected: HashSet<(String, String)> =
+            [("A", "H"), ("B", "H"), ("C", "H"), ("C", "D"), ("D", "E")]
+                .iter()
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .collect();
+        assert_eq!(edges, expected);
+        assert_eq!(tree[0].0, "A");
+        assert_eq!(tree.iter().map(|s| s.2).sum::<f64>(), 7.0);
+    }
+
+    #[test]
+    fn disconnected_terminals_are_an_error() {
+        let df = df!("source" => ["A", "C"], "sink" => ["B", "D"]).unwrap();
+        let graph =
+            UndirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+        assert!(graph.steiner_tree(series(&["A", "D"]), None).is_err());
+        assert!(graph.tsp_tour(None, None).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::undirected;
+    use polars::prelude::*;
+    use polars_graph::graph::UndirectedGraph;
+    use std::collections::HashSet;
+
+    /// triangles ABC and CDE, a bridge E-F and the complete graph on FGHI
+    fn accounts() -> UndirectedGraph {
+        undirected(
+            &[
+                "A", "B", "C", "C", "D", "E", "E", "F", "F", "F", "G", "G", "H",
+            ],
+            &[
+                "B", "C", "A", "D", "E", "C", "F", "G", "H", "I", "H", "I", "I",
+            ],
+        )
+    }
+
+    fn members(df: &DataFrame) -> HashSet<Vec<String>> {
+        df.column("members")
+            .unwrap()
+            .list()
+            .unwrap()
+            .into_no_null_iter()
+            .map(|clique| {
+                let mut names: Vec<String> = clique
+                    .str()
+                    .unwrap()
+                    .into_no_null_iter()
+                    .map(|s| s.to_string())
+                    .collect();
+                names.sort();
+                names
+            })
+            .collect()
+    }
+
+    fn clique(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn enumerates_maximal_cliques() {
+        let df = accounts().find_cliques(1, None).unwrap().0;
+        assert_eq!(
+            members(&df),
+            HashSet::from([
+                clique(&["A", "B", "C"]),
+                clique(&["C", "D", "E"]),
+                clique(&["E", "F"]),
+                clique(&["F", "G", "H", "I"]),
+            ])
+        );
+        assert_eq!(
+            df.column("members").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::String))
+        );
+    }
+
+    #[test]
+    fn filters_by_min_size() {
+        let df = accounts().find_cliques(4, None).unwrap().0;
+        assert_eq!(members(&df), HashSet::from([clique(&["F", "G", "H", "I"])]));
+    }
+
+    #[test]
+    fn stops_at_limit() {
+        let df = accounts().find_cliques(1, Some(2)).unwrap().0;
+        assert_eq!(df.height(), 2);
+        let ids: Vec<u32> = df
+            .column("clique_id")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(ids, vec![0, 1]);
+    }
+
+    #[test]
+    fn ignores_self_loops_and_parallel_edges() {
+        let graph = undirected(&["A", "A", "B", "A"], &["A", "B", "A", "B"]);
+        let df = graph.find_cliques(1, None).unwrap().0;
+        assert_eq!(members(&df), HashSet::from([clique(&["A", "B"])]));
+    }
+
+    #[test]
+    fn finds_maximum_clique() {
+        let best = accounts().max_clique().unwrap().series().unwrap();
+        let mut names: Vec<&str> = best.str().unwrap().into_no_null_iter().collect();
+        names.sort();
+        assert_eq!(names, vec!["F", "G", "H", "I"]);
+    }
+
+    #[test]
+    fn no_cliques_gives_typed_empty_frame() {
+        let df = accounts().find_cliques(5, None).unwrap().0;
+        assert_eq!(df.height(), 0);
+        assert_eq!(
+            df.column("members").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::String))
+        );
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, NodeData};
+    use polars_graph::ingest::{GraphOptions, KeyColumns};
+    use pyo3_polars::PyDataFrame;
+
+    fn key(tenant: &str, user: i64) -> NodeData {
+        NodeData::Tuple(vec![NodeData::String(tenant.into()), NodeData::Int(user)])
+    }
+
+    fn columns(names: &[&str]) -> KeyColumns {
+        KeyColumns::Many(names.iter().map(|n| n.to_string()).collect())
+    }
+
+    /// users 1 -> 2 -> 3 in tenant a, and a separate 1 -> 2 in tenant b
+    fn tenants() -> DirectedGraph {
+        let df = df!(
+            "tenant_id" => ["a", "a", "b"],
+            "user_id" => [1i64, 2, 1],
+            "to_tenant" => ["a", "a", "b"],
+            "to_user" => [2i64, 3, 2]
+        )
+        .unwrap();
+        DirectedGraph::with_options(
+            PyDataFrame(df),
+            columns(&["tenant_id", "user_id"]),
+            columns(&["to_tenant", "to_user"]),
+            None,
+            GraphOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn tuples_identify_nodes() {
+        let graph = tenants();
+        assert!(graph
+            .has_path(key("a", 1).into(), key("a", 3).into())
+            .unwrap());
+        assert!(!graph
+            .has_path(key("b", 1).into(), key("a", 2).into())
+            .unwrap());
+        assert!(graph
+            .has_path(key("c", 1).into(), key("a", 2).into())
+            .is_err());
+    }
+
+    #[test]
+    fn single_node_columns_expand_into_the_key_columns() {
+        let df = tenants().descendants(key("a", 1).into(), None).unwrap().0;
+        let names: Vec<&str> = df
+            .get_column_names()
+            .into_iter()
+            .map(|n| n.as_str())
+            .collect();
+        assert_eq!(names, vec!["tenant_id", "user_id", "depth"]);
+        let mut users: Vec<i64> = df
+            .column("user_id")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        users.sort();
+        assert_eq!(users, vec![2, 3]);
+    }
+
+    #[test]
+    fn several_node_columns_are_prefixed() {
+        let df = tenants()
+            .shortest_path(
+                key("a", 1).into(),
+                key("a", 3).into(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .0;
+        let names: Vec<&str> = df
+            .get_column_names()
+            .into_iter()
+            .map(|n| n.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "step",
+                "source_tenant_id",
+                "source_user_id",
+                "target_tenant_id",
+                "target_user_id",
+                "weight"
+            ]
+        );
+    }
+
+    fn names(df: &DataFrame) -> Vec<&str> {
+        df.get_column_names()
+            .into_iter()
+            .map(|name| name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn node_results_expand_into_the_key_columns() {
+        let roots = tenants().roots().unwrap().frame().unwrap();
+        assert_eq!(names(&roots), vec!["tenant_id", "user_id"]);
+        assert_eq!(roots.height(), 2);
+        assert!(tenants().leaves().unwrap().series().is_none());
+    }
+
+    #[test]
+    fn missing_ancestors_are_null_in_every_key_column() {
+        let key_column = |name: &str, tenants: [&str; 2], users: [i64; 2]| {
+            let parts = [
+                Series::new("tenant_id".into(), tenants),
+                Series::new("user_id".into(), users),
+            ];
+            StructChunked::from_series(name.into(), 2, parts.iter())
+                .unwrap()
+                .into_column()
+        };
+        let pairs = DataFrame::new(vec![
+            key_column("left", ["a", "a"], [2, 3]),
+            key_column("right", ["a", "b"], [3, 2]),
+        ])
+        .unwrap();
+        let lca = tenants()
+            .lowest_common_ancestor(PyDataFrame(pairs), "left".into(), "right".into())
+            .unwrap()
+            .frame()
+            .unwrap();
+        assert_eq!(names(&lca), vec!["tenant_id", "user_id"]);
+        assert_eq!(
+            lca.column("tenant_id").unwrap().str().unwrap().get(0),
+            Some("a")
+        );
+        assert_eq!(
+            lca.column("user_id").unwrap().i64().unwrap().get(0),
+            Some(2)
+        );
+        assert_eq!(lca.column("tenant_id").unwrap().null_count(), 1);
+        assert_eq!(lca.column("user_id").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn key_column_counts_must_match() {
+        let df = df!("a" => [1i64], "b" => [2i64], "c" => [3i64]).unwrap();
+        let graph = DirectedGraph::with_options(
+            PyDataFrame(df),
+            columns(&["a", "b"]),
+            columns(&["c"]),
+            None,
+            GraphOptions::default(),
+        );
+        assert!(graph.is_err());
+    }
+
+    #[test]
+    fn a_null_key_part_is_a_null_key() {
+        let df = df!(
+            "tenant_id" => [Some("a"), None],
+            "user_id" => [1i64, 2],
+            "to_tenant" => ["a", "a"],
+            "to_user" => [2i64, 3]
+        )
+        .unwrap();
+        let graph = DirectedGraph::with_options(
+            PyDataFrame(df),
+            columns(&["tenant_id", "user_id"]),
+            columns(&["to_tenant", "to_user"]),
+            None,
+            GraphOptions::default(),
+        );
+        assert!(graph.is_err());
+    }
+}
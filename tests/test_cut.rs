@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::UndirectedGraph;
+    use pyo3_polars::PyDataFrame;
+
+    fn weighted(sources: &[i64], sinks: &[i64], weights: &[f64]) -> UndirectedGraph {
+        let df = df!("source" => sources, "sink" => sinks, "weight" => weights).unwrap();
+        UndirectedGraph::new(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap()
+    }
+
+    fn sorted(series: &Series) -> Vec<i64> {
+        let mut nodes: Vec<i64> = series.i64().unwrap().into_no_null_iter().collect();
+        nodes.sort();
+        nodes
+    }
+
+    #[test]
+    fn finds_the_stoer_wagner_example_cut() {
+        let graph = weighted(
+            &[1, 1, 2, 2, 2, 3, 3, 4, 4, 5, 6, 7],
+            &[2, 5, 3, 5, 6, 4, 7, 7, 8, 6, 7, 8],
+            &[2.0, 3.0, 3.0, 2.0, 2.0, 4.0, 2.0, 2.0, 2.0, 3.0, 1.0, 3.0],
+        );
+        let (value, side, rest) = graph.minimum_cut(None).unwrap();
+        let side = side.series().unwrap();
+        let rest = rest.series().unwrap();
+        assert_eq!(value, 4.0);
+        let mut sides = [sorted(&side), sorted(&rest)];
+        sides.sort();
+        assert_eq!(sides, [vec![1, 2, 5, 6], vec![3, 4, 7, 8]]);
+    }
+
+    #[test]
+    fn parallel_edges_add_up() {
+        let graph = weighted(&[1, 1, 2], &[2, 2, 3], &[1.0, 1.0, 5.0]);
+        let (value, side, _) = graph.minimum_cut(None).unwrap();
+        let side = side.series().unwrap();
+        assert_eq!(value, 2.0);
+        assert_eq!(sorted(&side), vec![1]);
+    }
+
+    #[test]
+    fn disconnected_graph_has_a_zero_cut() {
+        let graph = weighted(&[1, 3], &[2, 4], &[1.0, 1.0]);
+        let (value, side, rest) = graph.minimum_cut(None).unwrap();
+        let side = side.series().unwrap();
+        let rest = rest.series().unwrap();
+        assert_eq!(value, 0.0);
+        assert_eq!(side.len() + rest.len(), 4);
+    }
+
+    #[test]
+    fn matches_brute_force_on_a_small_graph() {
+        let mut state = 7u64;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            state >> 33
+        };
+        let (mut sources, mut sinks, mut weights) = (Vec::new(), Vec::new(), Vec::new());
+        for a in 0..8i64 {
+            for b in (a + 1)..8 {
+                if next() % 2 == 0 {
+                    sources.push(a);
+                    sinks.push(b);
+                    weights.push((next() % 9 + 1) as f64);
+                }
+            }
+        }
+        // a ring guarantees every node appears
+        for a in 0..8i64 {
+            sources.push(a);
+            sinks.push((a + 1) % 8);
+            weights.push(1.0);
+        }
+        let graph = weighted(&sources, &sinks, &weights);
+
+        let mut expected = f64::INFINITY;
+        for mask in 1u32..(1 << 8) - 1 {
+            let crossing: f64 = sources
+                .iter()
+                .zip(&sinks)
+                .zip(&weights)
+                .filter(|((a, b), _)| (mask >> **a & 1) != (mask >> **b & 1))
+                .map(|(_, w)| w)
+                .sum();
+            expected = expected.min(crossing);
+        }
+        let (value, side, _) = graph.minimum_cut(None).unwrap();
+        let side = side.series().unwrap();
+        assert_eq!(value, expected);
+
+        let side = sorted(&side);
+        let crossing: f64 = sources
+            .iter()
+            .zip(&sinks)
+            .zip(&weights)
+            .filter(|((a, b), _)| side.contains(a) != side.contains(b))
+            .map(|(_, w)| w)
+            .sum();
+        assert_eq!(crossing, value);
+    }
+
+    #[test]
+    fn needs_two_nodes_and_non_negative_weights() {
+        let graph = weighted(&[1], &[1], &[1.0]);
+        assert!(graph.minimum_cut(None).is_err());
+        let graph = weighted(&[1, 2], &[2, 3], &[1.0, -1.0]);
+        assert!(graph.minimum_cut(None).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{directed, undirected};
+    use polars::prelude::*;
+    use std::collections::HashSet;
+
+    fn cycles(df: &DataFrame) -> Vec<Vec<String>> {
+        df.column("cycle")
+            .unwrap()
+            .list()
+            .unwrap()
+            .into_no_null_iter()
+            .map(|cycle| {
+                cycle
+                    .str()
+                    .unwrap()
+                    .into_no_null_iter()
+                    .map(|s| s.to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// rotates each cycle to start at its smallest node so cycles compare as sets
+    fn normalised(found: Vec<Vec<String>>) -> HashSet<Vec<String>> {
+        found
+            .into_iter()
+            .map(|mut cycle| {
+                let start = (0..cycle.len()).min_by_key(|&i| cycle[i].clone()).unwrap();
+                cycle.rotate_left(start);
+                cycle
+            })
+            .collect()
+    }
+
+    mod directed_graphs {
+        use super::*;
+
+        #[test]
+        fn finds_individual_cycles_not_components() {
+            let graph = directed(&["A", "B", "B", "C", "C"], &["B", "A", "C", "A", "C"]);
+            assert!(graph.is_cyclic());
+            let found = normalised(cycles(&graph.simple_cycles(None).unwrap().0));
+            let expected: HashSet<Vec<String>> = [vec!["C"], vec!["A", "B"], vec!["A", "B", "C"]]
+                .into_iter()
+                .map(|c| c.into_iter().map(String::from).collect())
+                .collect();
+            assert_eq!(found, expected);
+        }
+
+        #[test]
+        fn complete_digraph_and_limit() {
+            let graph = directed(
+                &["A", "A", "B", "B", "C", "C"],
+                &["B", "C", "A", "C", "A", "B"],
+            );
+            assert_eq!(graph.simple_cycles(None).unwrap().0.height(), 5);
+            assert_eq!(graph.simple_cycles(Some(2)).unwrap().0.height(), 2);
+        }
+
+        #[test]
+        fn acyclic_graph_has_no_cycles() {
+            let graph = directed(&["A", "A", "B"], &["B", "C", "C"]);
+            assert!(!graph.is_cyclic());
+            assert_eq!(graph.simple_cycles(None).unwrap().0.height(), 0);
+        }
+    }
+
+    mod undirected_graphs {
+        use super::*;
+
+        #[test]
+        fn cycle_basis_has_one_cycle_per_extra_edge() {
+            let graph = undirected(&["A", "B", "C", "D", "A"], &["B", "C", "D", "A", "C"]);
+            assert!(graph.is_cyclic());
+            let basis = cycles(&graph.cycle_basis().unwrap().0);
+            assert_eq!(basis.len(), 2);
+            let edges: HashSet<(String, String)> =
+                [("A", "B"), ("B", "C"), ("C", "D"), ("D", "A"), ("A", "C")]
+                    .into_iter()
+                    .flat_map(|(a, b)| {
+                        [
+                            (a.to_string(), b.to_string()),
+                            (b.to_string(), a.to_string()),
+                        ]
+                    })
+                    .collect();
+            for cycle in basis {
+                assert_eq!(cycle.len(), 3);
+                for i in 0..cycle.len() {
+                    let next = &cycle[(i + 1) % cycle.len()];
+                    assert!(edges.contains(&(cycle[i].clone(), next.clone())));
+                }
+            }
+        }
+
+        #[test]
+        fn parallel_edges_and_self_loops_are_cycles() {
+            let graph = undirected(&["A", "A", "C"], &["B", "B", "C"]);
+            let basis = normalised(cycles(&graph.cycle_basis().unwrap().0));
+            let expected: HashSet<Vec<String>> = [vec!["A", "B"], vec!["C"]]
+                .into_iter()
+                .map(|c| c.into_iter().map(String::from).collect())
+                .collect();
+            assert_eq!(basis, expected);
+        }
+
+        #[test]
+        fn tree_is_acyclic() {
+            let graph = undirected(&["A", "A", "B"], &["B", "C", "D"]);
+            assert!(!graph.is_cyclic());
+            assert_eq!(graph.cycle_basis().unwrap().0.height(), 0);
+        }
+    }
+}
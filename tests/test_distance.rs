@@ -0,0 +1,146 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::weighted_edges;
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, UndirectedGraph};
+    use std::collections::HashSet;
+
+    /// A - B - C - D with a heavy middle edge
+    fn path() -> UndirectedGraph {
+        UndirectedGraph::new(
+            weighted_edges(&["A", "B", "C"], &["B", "C", "D"], &[1.0, 5.0, 1.0]),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap()
+    }
+
+    fn names(series: &Series) -> HashSet<String> {
+        series
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    mod undirected_graphs {
+        use super::*;
+
+        #[test]
+        fn hop_metrics() {
+            let graph = path();
+            assert_eq!(graph.diameter(false, false, None).unwrap(), 3.0);
+            assert_eq!(graph.radius(false, None).unwrap(), 2.0);
+            assert_eq!(
+                names(&graph.center(false, None).unwrap().series().unwrap()),
+                HashSet::from(["B".into(), "C".into()])
+            );
+            assert_eq!(
+                names(&graph.periphery(false, None).unwrap().series().unwrap()),
+                HashSet::from(["A".into(), "D".into()])
+            );
+        }
+
+        #[test]
+        fn a_weight_column_needs_weighted() {
+            let graph = path();
+            let weight = || Some("weight".to_string());
+            assert!(graph.eccentricity(false, weight()).is_err());
+            assert!(graph.diameter(false, false, weight()).is_err());
+            assert!(graph.diameter(false, true, weight()).is_err());
+            assert!(graph.radius(false, weight()).is_err());
+            assert!(graph.center(false, weight()).is_err());
+            assert!(graph.periphery(false, weight()).is_err());
+            assert_eq!(graph.radius(true, weight()).unwrap(), 6.0);
+        }
+
+        #[test]
+        fn weighted_metrics() {
+            let graph = path();
+            assert_eq!(graph.diameter(true, false, None).unwrap(), 7.0);
+            assert_eq!(graph.radius(true, None).unwrap(), 6.0);
+
+            let ecc = graph.eccentricity(true, None).unwrap().0;
+            assert_eq!(ecc.height(), 4);
+            let values: Vec<f64> = ecc
+                .column("eccentricity")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .into_no_null_iter()
+                .collect();
+            assert_eq!(values.iter().sum::<f64>(), 26.0);
+        }
+
+        #[test]
+        fn double_sweep_finds_path_diameter() {
+            let graph = path();
+            assert_eq!(graph.diameter(false, true, None).unwrap(), 3.0);
+            assert_eq!(graph.diameter(true, true, None).unwrap(), 7.0);
+        }
+
+        #[test]
+        fn disconnected_graph_is_an_error() {
+            let graph = UndirectedGraph::new(
+                weighted_edges(&["A", "C"], &["B", "D"], &[1.0, 1.0]),
+                "source".into(),
+                "sink".into(),
+                None,
+            )
+            .unwrap();
+            assert!(graph.diameter(false, false, None).is_err());
+            assert!(graph.diameter(false, true, None).is_err());
+            assert!(graph.eccentricity(false, None).is_err());
+        }
+
+        #[test]
+        fn negative_weights_are_rejected() {
+            let graph = UndirectedGraph::new(
+                weighted_edges(&["A"], &["B"], &[-1.0]),
+                "source".into(),
+                "sink".into(),
+                Some("weight".into()),
+            )
+            .unwrap();
+            assert!(graph.radius(true, None).is_err());
+            assert_eq!(graph.radius(false, None).unwrap(), 1.0);
+        }
+    }
+
+    mod directed_graphs {
+        use super::*;
+
+        #[test]
+        fn strongly_connected_cycle() {
+            let graph = DirectedGraph::new(
+                weighted_edges(&["A", "B", "C"], &["B", "C", "A"], &[1.0, 1.0, 1.0]),
+                "source".into(),
+                "sink".into(),
+                None,
+            )
+            .unwrap();
+            assert_eq!(graph.diameter(false, false, None).unwrap(), 2.0);
+            assert_eq!(graph.radius(false, None).unwrap(), 2.0);
+            assert_eq!(
+                graph.center(false, None).unwrap().series().unwrap().len(),
+                3
+            );
+        }
+
+        #[test]
+        fn one_way_path_is_not_strongly_connected() {
+            let graph = DirectedGraph::new(
+                weighted_edges(&["A", "B"], &["B", "C"], &[1.0, 1.0]),
+                "source".into(),
+                "sink".into(),
+                None,
+            )
+            .unwrap();
+            assert!(graph.radius(false, None).is_err());
+        }
+    }
+}
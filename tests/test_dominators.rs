@@ -0,0 +1,98 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{directed, node};
+    use polars::prelude::*;
+    use polars_graph::graph::DirectedGraph;
+    use std::collections::{HashMap, HashSet};
+
+    /// R -> A -> C -> D, R -> B -> C, D -> A and an unreachable X -> R
+    fn control_flow() -> DirectedGraph {
+        directed(
+            &["R", "R", "A", "B", "C", "D", "X"],
+            &["A", "B", "C", "C", "D", "A", "R"],
+        )
+    }
+
+    fn pairs(df: &DataFrame, left: &str, right: &str) -> Vec<(String, Option<String>)> {
+        let left = df.column(left).unwrap().str().unwrap().clone();
+        let right = df.column(right).unwrap().str().unwrap().clone();
+        left.into_no_null_iter()
+            .zip(&right)
+            .map(|(l, r)| (l.to_string(), r.map(|r| r.to_string())))
+            .collect()
+    }
+
+    mod immediate_dominators {
+        use super::*;
+
+        #[test]
+        fn finds_idom_of_each_reachable_node() {
+            let df = control_flow()
+                .immediate_dominators(node("R").into())
+                .unwrap()
+                .0;
+            let idoms: HashMap<String, Option<String>> =
+                pairs(&df, "node", "idom").into_iter().collect();
+
+            assert_eq!(idoms.len(), 5);
+            assert_eq!(idoms["R"], None);
+            assert_eq!(idoms["A"], Some("R".to_string()));
+            assert_eq!(idoms["B"], Some("R".to_string()));
+            assert_eq!(idoms["C"], Some("R".to_string()));
+            assert_eq!(idoms["D"], Some("C".to_string()));
+        }
+
+        #[test]
+        fn skips_unreachable_nodes() {
+            let df = control_flow()
+                .immediate_dominators(node("C").into())
+                .unwrap()
+                .0;
+            let nodes: HashSet<String> = pairs(&df, "node", "idom")
+                .into_iter()
+                .map(|(n, _)| n)
+                .collect();
+            assert_eq!(nodes, HashSet::from(["C", "D", "A"].map(String::from)));
+        }
+
+        #[test]
+        fn rejects_unknown_root() {
+            assert!(control_flow()
+                .immediate_dominators(node("Z").into())
+                .is_err());
+        }
+    }
+
+    mod dominance_frontier {
+        use super::*;
+
+        #[test]
+        fn collects_join_points() {
+            let df = control_flow()
+                .dominance_frontier(node("R").into())
+                .unwrap()
+                .0;
+            let frontier: HashSet<(String, String)> = pairs(&df, "node", "frontier")
+                .into_iter()
+                .map(|(n, f)| (n, f.unwrap()))
+                .collect();
+            let expected: HashSet<(String, String)> =
+                [("A", "C"), ("B", "C"), ("C", "A"), ("D", "A")]
+                    .into_iter()
+                    .map(|(n, f)| (n.to_string(), f.to_string()))
+                    .collect();
+            assert_eq!(frontier, expected);
+        }
+
+        #[test]
+        fn loop_back_to_root_is_in_frontier() {
+            let graph = directed(&["R", "A"], &["A", "R"]);
+            let df = graph.dominance_frontier(node("R").into()).unwrap().0;
+            let frontier = pairs(&df, "node", "frontier");
+            assert!(frontier.contains(&("A".to_string(), Some("R".to_string()))));
+            assert!(frontier.contains(&("R".to_string(), Some("R".to_string()))));
+        }
+    }
+}
@@ -0,0 +1,339 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::expressions::{cycle_lists, list_dtype};
+    use polars_graph::graph::{
+        DirectedGraph, GraphError, NodeArg, NodeData, NodeSchema, UndirectedGraph,
+    };
+    use polars_graph::ingest::{GraphOptions, KeyColumns};
+    use pyo3::prelude::*;
+    use pyo3_polars::{PyDataFrame, PySeries};
+    use std::ffi::CString;
+
+    fn directed(sources: Series, sinks: Series) -> DirectedGraph {
+        let df = DataFrame::new(vec![
+            sources.with_name("source".into()).into(),
+            sinks.with_name("sink".into()).into(),
+        ])
+        .unwrap();
+        DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap()
+    }
+
+    /// a chain a -> b -> c in whatever dtype the values were cast to
+    fn chain(dtype: &DataType, values: Series) -> DirectedGraph {
+        let values = values.cast(dtype).unwrap();
+        directed(values.slice(0, 2), values.slice(1, 2))
+    }
+
+    fn roots_of(graph: &DirectedGraph) -> Series {
+        graph.roots().unwrap().series().unwrap()
+    }
+
+    mod round_trip {
+        use super::*;
+
+        #[test]
+        fn integer_widths_are_kept() {
+            for dtype in [
+                DataType::Int8,
+                DataType::Int16,
+                DataType::Int32,
+                DataType::UInt8,
+                DataType::UInt16,
+                DataType::UInt32,
+                DataType::UInt64,
+            ] {
+                let graph = chain(&dtype, Series::new("".into(), [1i64, 2, 3]));
+                let roots = roots_of(&graph);
+                assert_eq!(roots.dtype(), &dtype);
+                assert_eq!(
+                    roots.cast(&DataType::Int64).unwrap().i64().unwrap().get(0),
+                    Some(1)
+                );
+                assert!(graph
+                    .has_path(NodeData::Int(1).into(), NodeData::Int(3).into())
+                    .unwrap());
+            }
+        }
+
+        #[test]
+        fn unsigned_values_beyond_i64() {
+            let big = u64::MAX - 1;
+            let graph = directed(
+                Series::new("".into(), [big, 1]),
+                Series::new("".into(), [1u64, 2]),
+            );
+            let roots = roots_of(&graph);
+            assert_eq!(roots.u64().unwrap().get(0), Some(big));
+            assert!(graph
+                .has_path(NodeData::UInt(big).into(), NodeData::Int(2).into())
+                .unwrap());
+        }
+
+        #[test]
+        fn float32_is_kept() {
+            let graph = chain(
+                &DataType::Float32,
+                Series::new("".into(), [0.5f64, 1.5, 2.5]),
+            );
+            assert_eq!(roots_of(&graph).dtype(), &DataType::Float32);
+        }
+
+        #[test]
+        fn categorical_and_enum_labels() {
+            let labels = Series::new("".into(), ["a", "b", "c"]);
+            let categorical = DataType::from_categories(Categories::global());
+            let graph = chain(&categorical, labels.clone());
+            let roots = roots_of(&graph);
+            assert!(matches!(roots.dtype(), DataType::Categorical(..)));
+            assert_eq!(
+                roots.cast(&DataType::String).unwrap().str().unwrap().get(0),
+                Some("a")
+            );
+
+            let enumeration =
+                DataType::from_frozen_categories(FrozenCategories::new(["c", "b", "a"]).unwrap());
+            let graph = chain(&enumeration, labels);
+            assert_eq!(roots_of(&graph).dtype(), &enumeration);
+            assert!(graph
+                .has_path(
+                    NodeData::String("a".into()).into(),
+                    NodeData::String("c".into()).into()
+                )
+                .unwrap());
+        }
+
+        #[test]
+        fn dates_and_datetimes() {
+            let days = Series::new("".into(), [19000i32, 19001, 19002]);
+            let graph = chain(&DataType::Date, days);
+            let path = graph
+                .shortest_path(
+                    NodeData::Int(19000).into(),
+                    NodeData::Int(19002).into(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()
+                .0;
+            assert_eq!(path.column("source").unwrap().dtype(), &DataType::Date);
+            assert_eq!(path.height(), 2);
+
+            let stamp = DataType::Datetime(TimeUnit::Milliseconds, None);
+            let millis = Series::new("".into(), [1_700_000_000_000i64, 1_700_000_001_000, 1]);
+            let graph = chain(&stamp, millis);
+            assert_eq!(roots_of(&graph).dtype(), &stamp);
+        }
+
+        #[test]
+        fn binary_hashes() {
+            let hashes = Series::new("".into(), [b"\x00\x01".as_slice(), b"\xff", b"\x10\x20"]);
+            let graph = chain(&DataType::Binary, hashes);
+            let roots = roots_of(&graph);
+            assert_eq!(roots.binary().unwrap().get(0), Some(b"\x00\x01".as_slice()));
+            assert!(graph
+                .has_path(
+                    NodeData::Binary(vec![0, 1]).into(),
+                    NodeData::Binary(vec![0x10, 0x20]).into()
+                )
+                .unwrap());
+        }
+
+        #[test]
+        fn list_results_keep_the_dtype() {
+            let df = df!("source" => [1u32, 2, 3], "sink" => [2u32, 3, 1]).unwrap();
+            let graph = UndirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None)
+                .unwrap();
+            let basis = graph.cycle_basis().unwrap().0;
+            assert_eq!(
+                basis.column("cycle").unwrap().dtype(),
+                &DataType::List(Box::new(DataType::UInt32))
+            );
+        }
+    }
+
+    mod lookups {
+        use super::*;
+
+        #[test]
+        fn node_series_in_other_dtypes_are_matched() {
+            let graph = chain(
+                &DataType::Date,
+                Series::new("".into(), [19000i32, 19001, 19002]),
+            );
+            let sources = Series::new("".into(), [19001i32])
+                .cast(&DataType::Date)
+                .unwrap();
+            let reached = graph.multi_source_bfs(PySeries(sources), None).unwrap().0;
+            assert_eq!(reached.height(), 2);
+            assert_eq!(reached.column("node").unwrap().dtype(), &DataType::Date);
+        }
+
+        #[test]
+        fn mismatched_source_and_sink_dtypes_are_rejected() {
+            let df = df!("source" => [1i64, 2], "sink" => ["a", "b"]).unwrap();
+            assert!(
+                DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).is_err()
+            );
+        }
+
+        #[test]
+        fn mixed_integer_widths_share_keys() {
+            let graph = directed(
+                Series::new("".into(), [1i32, 2]),
+                Series::new("".into(), [2i64, 3]),
+            );
+            assert!(graph
+                .has_path(NodeData::Int(1).into(), NodeData::Int(3).into())
+                .unwrap());
+        }
+    }
+
+    mod python_values {
+        use super::*;
+
+        /// a node argument as python passes it, `datetime` is imported for the expression
+        fn arg(expression: &str) -> NodeArg {
+            pyo3::prepare_freethreaded_python();
+            Python::with_gil(|py| {
+                let code = format!("(lambda datetime: {})(__import__('datetime'))", expression);
+                let value = py.eval(&CString::new(code).unwrap(), None, None).unwrap();
+                value.extract().unwrap()
+            })
+        }
+
+        #[test]
+        fn dates_name_date_nodes() {
+            let graph = chain(
+                &DataType::Date,
+                Series::new("".into(), [19000i32, 19001, 19002]),
+            );
+            let path = graph
+                .shortest_path(
+                    arg("datetime.date(2022, 1, 8)"),
+                    arg("datetime.date(2022, 1, 10)"),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()
+                .0;
+            assert_eq!(path.height(), 2);
+        }
+
+        #[test]
+        fn datetimes_are_read_in_the_key_unit() {
+            let stamp = DataType::Datetime(TimeUnit::Milliseconds, None);
+            let millis = Series::new("".into(), [1_700_000_000_000i64, 1_700_000_001_000, 1]);
+            let graph = chain(&stamp, millis);
+            let naive = arg("datetime.datetime(2023, 11, 14, 22, 13, 20)");
+            let aware = arg("datetime.datetime(2023, 11, 14, 23, 13, 20, \
+                 tzinfo=datetime.timezone(datetime.timedelta(hours=1)))");
+            let last = arg("datetime.datetime(1970, 1, 1, 0, 0, 0, 1000)");
+            assert!(graph.has_path(naive, last.clone()).unwrap());
+            assert!(graph.has_path(aware, last).unwrap());
+        }
+
+        #[test]
+        fn times_and_durations_name_their_nodes() {
+            let hours = Series::new(
+                "".into(),
+                [3_600_000_000_000i64, 7_200_000_000_000, 10_800_000_000_000],
+            );
+            let graph = chain(&DataType::Time, hours);
+            assert!(graph
+                .has_path(arg("datetime.time(1)"), arg("datetime.time(3)"))
+                .unwrap());
+
+            let seconds = Series::new("".into(), [1_000_000i64, 2_000_000, 3_000_000]);
+            let graph = chain(&DataType::Duration(TimeUnit::Microseconds), seconds);
+            assert!(graph
+                .has_path(
+                    arg("datetime.timedelta(seconds=1)"),
+                    arg("datetime.timedelta(seconds=3)")
+                )
+                .unwrap());
+        }
+
+        #[test]
+        fn integers_name_float_nodes() {
+            let graph = chain(
+                &DataType::Float64,
+                Series::new("".into(), [1.0f64, 2.0, 3.0]),
+            );
+            assert!(graph.has_path(arg("1"), arg("3")).unwrap());
+        }
+
+        #[test]
+        fn tuples_are_coerced_per_field() {
+            let day = |days: [i32; 2]| Series::new("".into(), days).cast(&DataType::Date).unwrap();
+            let df = df!(
+                "id" => [1i64, 2],
+                "day" => day([19000, 19001]),
+                "to_id" => [2i64, 3],
+                "to_day" => day([19001, 19002])
+            )
+            .unwrap();
+            let graph = DirectedGraph::with_options(
+                PyDataFrame(df),
+                KeyColumns::Many(vec!["id".into(), "day".into()]),
+                KeyColumns::Many(vec!["to_id".into(), "to_day".into()]),
+                None,
+                GraphOptions::default(),
+            )
+            .unwrap();
+            assert!(graph
+                .has_path(
+                    arg("(1, datetime.date(2022, 1, 8))"),
+                    arg("(3.0, datetime.date(2022, 1, 10))")
+                )
+                .is_err());
+            assert!(graph
+                .has_path(
+                    arg("(1, datetime.date(2022, 1, 8))"),
+                    arg("(3, datetime.date(2022, 1, 10))")
+                )
+                .unwrap());
+        }
+
+        #[test]
+        fn temporal_values_do_not_name_other_nodes() {
+            let schema = NodeSchema::new(&DataType::Int64).unwrap();
+            assert!(matches!(
+                schema.coerce(arg("datetime.date(1970, 1, 2)")),
+                Err(GraphError::InvalidDataType(_))
+            ));
+            assert_eq!(schema.coerce(arg("2")).unwrap(), NodeData::Int(2));
+        }
+    }
+
+    mod expressions {
+        use super::*;
+
+        #[test]
+        fn list_dtype_accepts_every_node_dtype() {
+            for dtype in [
+                DataType::UInt32,
+                DataType::Date,
+                DataType::Binary,
+                DataType::from_categories(Categories::global()),
+            ] {
+                let field = list_dtype(&[Field::new("a".into(), dtype.clone())]).unwrap();
+                assert_eq!(field.dtype(), &DataType::List(Box::new(dtype)));
+            }
+            assert!(list_dtype(&[Field::new("a".into(), DataType::Boolean)]).is_err());
+        }
+
+        #[test]
+        fn cycle_lists_return_the_input_dtype() {
+            let sources = Series::new("a".into(), [Some(1u32), Some(2), None, Some(3)]);
+            let sinks = Series::new("b".into(), [Some(2u32), Some(1), Some(3), Some(4)]);
+            let cycles = cycle_lists(&sources, &sinks).unwrap();
+            assert_eq!(cycles.dtype(), &DataType::List(Box::new(DataType::UInt32)));
+            assert_eq!(cycles.len(), 1);
+        }
+    }
+}
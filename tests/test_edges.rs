@@ -0,0 +1,280 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::node;
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, UndirectedGraph};
+    use polars_graph::ingest::{GraphOptions, KeyColumns};
+    use pyo3::Python;
+    use pyo3_polars::PyDataFrame;
+
+    /// two routes from a to c: direct, or through b which is cheaper but slower
+    fn routes() -> DataFrame {
+        df!(
+            "source" => ["a", "a", "b"],
+            "sink" => ["c", "b", "c"],
+            "distance" => [10.0, 4.0, 4.0],
+            "cost" => [5.0, 1.0, 1.0],
+            "latency" => [1i64, 5, 5],
+            "carrier" => ["x", "y", "y"]
+        )
+        .unwrap()
+    }
+
+    fn hops(df: &DataFrame) -> Vec<String> {
+        df.column("target")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn edges_carry_the_input_columns() {
+        let graph = DirectedGraph::new(
+            PyDataFrame(routes()),
+            "source".into(),
+            "sink".into(),
+            Some("distance".into()),
+        )
+        .unwrap();
+        let df = graph.edges().unwrap().0;
+        let names: Vec<&str> = df
+            .get_column_names()
+            .into_iter()
+            .map(|n| n.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["source", "target", "weight", "cost", "latency", "carrier"]
+        );
+        let carriers: Vec<&str> = df
+            .column("carrier")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(carriers, vec!["x", "y", "y"]);
+    }
+
+    #[test]
+    fn the_weight_is_chosen_per_call() {
+        let graph = DirectedGraph::new(
+            PyDataFrame(routes()),
+            "source".into(),
+            "sink".into(),
+            Some("distance".into()),
+        )
+        .unwrap();
+        let stored = graph
+            .shortest_path(node("a").into(), node("c").into(), None, None, None, None)
+            .unwrap()
+            .0;
+        assert_eq!(hops(&stored), vec!["b", "c"]);
+        let fast = graph
+            .shortest_path(
+                node("a").into(),
+                node("c").into(),
+                Some("latency".into()),
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+            .0;
+        assert_eq!(hops(&fast), vec!["c"]);
+        assert_eq!(
+            fast.column("weight").unwrap().f64().unwrap().get(0),
+            Some(1.0)
+        );
+        let cheap = graph
+            .shortest_path_length(
+                node("a").into(),
+                node("c").into(),
+                Some("cost".into()),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(cheap, Some(2.0));
+    }
+
+    #[test]
+    fn unknown_or_non_numeric_weight_columns_fail() {
+        let graph = DirectedGraph::new(PyDataFrame(routes()), "source".into(), "sink".into(), None)
+            .unwrap();
+        assert!(graph
+            .shortest_path(
+                node("a").into(),
+                node("c").into(),
+                Some("missing".into()),
+                None,
+                None,
+                None
+            )
+            .is_err());
+        assert!(graph
+            .shortest_path(
+                node("a").into(),
+                node("c").into(),
+                Some("carrier".into()),
+                None,
+                None,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn null_weights_are_reported_by_edge() {
+        let df = df!(
+            "source" => ["a", "a", "b"],
+            "sink" => ["c", "b", "c"],
+            "cost" => [Some(5.0), None, Some(1.0)]
+        )
+        .unwrap();
+        let graph =
+            DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+        let err = graph
+            .shortest_path_length(
+                node("a").into(),
+                node("c").into(),
+                Some("cost".into()),
+                None,
+                None,
+                None,
+            )
+            .unwrap_err();
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let message = err.value(py).to_string();
+            assert!(message.starts_with("Invalid rows"), "{}", message);
+            assert!(message.ends_with("at rows [1]"), "{}", message);
+        });
+    }
+
+    #[test]
+    fn undirected_algorithms_take_a_weight_too() {
+        let graph =
+            UndirectedGraph::new(PyDataFrame(routes()), "source".into(), "sink".into(), None)
+                .unwrap();
+        let (by_count, _, _) = graph.minimum_cut(None).unwrap();
+        assert_eq!(by_count, 2.0);
+        let (by_cost, side, _) = graph.minimum_cut(Some("cost".into())).unwrap();
+        assert_eq!(by_cost, 2.0);
+        assert_eq!(side.series().unwrap().len(), 1);
+        assert_eq!(graph.edges().unwrap().0.width(), 7);
+    }
+
+    fn column_names(df: &DataFrame) -> Vec<&str> {
+        df.get_column_names()
+            .into_iter()
+            .map(|n| n.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn an_unread_weight_attribute_is_prefixed() {
+        let df = df!("from" => ["a"], "to" => ["b"], "weight" => [3.0]).unwrap();
+        let graph = DirectedGraph::new(PyDataFrame(df), "from".into(), "to".into(), None).unwrap();
+        let edges = graph.edges().unwrap().0;
+        assert_eq!(
+            column_names(&edges),
+            vec!["source", "target", "weight", "attr_weight"]
+        );
+        assert_eq!(
+            edges.column("weight").unwrap().f64().unwrap().get(0),
+            Some(1.0)
+        );
+        // the attribute keeps its own name as a weight
+        let length = graph
+            .shortest_path_length(
+                node("a").into(),
+                node("b").into(),
+                Some("weight".into()),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(length, Some(3.0));
+    }
+
+    #[test]
+    fn a_weight_attribute_beside_another_weight_column_is_prefixed() {
+        let df = df!("from" => ["a"], "to" => ["b"], "cost" => [2.0], "weight" => [3.0]).unwrap();
+        let graph = DirectedGraph::new(
+            PyDataFrame(df),
+            "from".into(),
+            "to".into(),
+            Some("cost".into()),
+        )
+        .unwrap();
+        let edges = graph.edges().unwrap().0;
+        assert_eq!(
+            column_names(&edges),
+            vec!["source", "target", "weight", "attr_weight"]
+        );
+        assert_eq!(
+            edges.column("weight").unwrap().f64().unwrap().get(0),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn source_and_target_attributes_are_prefixed() {
+        let df = df!(
+            "from" => ["a"],
+            "to" => ["b"],
+            "source" => ["feed"],
+            "target" => ["sink"],
+            "attr_target" => ["taken"]
+        )
+        .unwrap();
+        let graph = DirectedGraph::new(PyDataFrame(df), "from".into(), "to".into(), None).unwrap();
+        let edges = graph.edges().unwrap().0;
+        assert_eq!(
+            column_names(&edges),
+            vec![
+                "source",
+                "target",
+                "weight",
+                "attr_source",
+                "attr_attr_target",
+                "attr_target"
+            ]
+        );
+        assert_eq!(
+            edges.column("attr_source").unwrap().str().unwrap().get(0),
+            Some("feed")
+        );
+    }
+
+    #[test]
+    fn a_node_attribute_is_prefixed() {
+        let nodes = df!("id" => ["a", "b"], "node" => [1i64, 2]).unwrap();
+        let graph = DirectedGraph::with_options(
+            PyDataFrame(df!("from" => ["a"], "to" => ["b"]).unwrap()),
+            "from".into(),
+            "to".into(),
+            None,
+            GraphOptions {
+                nodes: Some(nodes),
+                node_key: Some(KeyColumns::from("id")),
+                ..GraphOptions::default()
+            },
+        )
+        .unwrap();
+        let df = graph.nodes().unwrap().0;
+        assert_eq!(column_names(&df), vec!["node", "attr_node"]);
+        assert_eq!(
+            df.column("attr_node").unwrap().i64().unwrap().get(1),
+            Some(2)
+        );
+    }
+}
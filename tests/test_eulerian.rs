@@ -0,0 +1,120 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{directed, node, undirected};
+    use polars::prelude::*;
+
+    fn column(df: &DataFrame, name: &str) -> Vec<String> {
+        df.column(name)
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    mod directed_graphs {
+        use super::*;
+
+        #[test]
+        fn detects_circuit() {
+            let graph = directed(&["A", "B", "C"], &["B", "C", "A"]);
+            assert!(graph.has_eulerian_path());
+            assert!(graph.has_eulerian_circuit());
+        }
+
+        #[test]
+        fn detects_open_path() {
+            let graph = directed(&["A", "B", "C"], &["B", "C", "D"]);
+            assert!(graph.has_eulerian_path());
+            assert!(!graph.has_eulerian_circuit());
+        }
+
+        #[test]
+        fn rejects_unbalanced_graph() {
+            let graph = directed(&["A", "A", "A"], &["B", "C", "D"]);
+            assert!(!graph.has_eulerian_path());
+            assert!(graph.eulerian_path(None).is_err());
+        }
+
+        #[test]
+        fn rejects_disconnected_edges() {
+            let graph = directed(&["A", "B", "C", "D"], &["B", "A", "D", "C"]);
+            assert!(!graph.has_eulerian_path());
+        }
+
+        #[test]
+        fn walks_every_edge_once_in_order() {
+            let graph = directed(&["A", "B", "C", "B", "D"], &["B", "C", "B", "D", "E"]);
+            let walk = graph.eulerian_path(None).unwrap().0;
+            let sources = column(&walk, "source");
+            let targets = column(&walk, "target");
+
+            assert_eq!(walk.height(), 5);
+            assert_eq!(sources[0], "A");
+            assert_eq!(targets[4], "E");
+            for step in 1..walk.height() {
+                assert_eq!(sources[step], targets[step - 1]);
+            }
+        }
+
+        #[test]
+        fn rejects_invalid_start() {
+            let graph = directed(&["A", "B"], &["B", "C"]);
+            let start = node("C");
+            assert!(graph.eulerian_path(Some(start.into())).is_err());
+        }
+
+        #[test]
+        fn rejects_unknown_start() {
+            let graph = directed(&["A", "B"], &["B", "A"]);
+            let start = node("Z");
+            assert!(graph.eulerian_path(Some(start.into())).is_err());
+        }
+    }
+
+    mod undirected_graphs {
+        use super::*;
+
+        #[test]
+        fn detects_circuit_with_self_loop() {
+            let graph = undirected(&["A", "B", "C", "A"], &["B", "C", "A", "A"]);
+            assert!(graph.has_eulerian_circuit());
+            assert_eq!(graph.eulerian_path(None).unwrap().0.height(), 4);
+        }
+
+        #[test]
+        fn starts_from_either_odd_node() {
+            let graph = undirected(&["A", "B", "B", "C"], &["B", "C", "D", "D"]);
+            assert!(graph.has_eulerian_path());
+            assert!(!graph.has_eulerian_circuit());
+
+            let start = node("B");
+            let walk = graph.eulerian_path(Some(start.into())).unwrap().0;
+            let sources = column(&walk, "source");
+            let targets = column(&walk, "target");
+
+            assert_eq!(sources[0], "B");
+            assert_eq!(targets[walk.height() - 1], "A");
+            for step in 1..walk.height() {
+                assert_eq!(sources[step], targets[step - 1]);
+            }
+        }
+
+        #[test]
+        fn rejects_even_node_start_for_open_path() {
+            let graph = undirected(&["A", "B", "B", "C"], &["B", "C", "D", "D"]);
+            let start = node("C");
+            assert!(graph.eulerian_path(Some(start.into())).is_err());
+        }
+
+        #[test]
+        fn empty_graph_has_empty_circuit() {
+            let graph = undirected(&[], &[]);
+            assert!(graph.has_eulerian_circuit());
+            assert_eq!(graph.eulerian_path(None).unwrap().0.height(), 0);
+        }
+    }
+}
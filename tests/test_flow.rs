@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::DirectedGraph;
+    use pyo3::{PyResult, Python};
+    use pyo3_polars::PyDataFrame;
+    use std::collections::HashMap;
+
+    fn network() -> DirectedGraph {
+        let df = df!(
+            "source" => ["S", "S", "A", "A", "B"],
+            "sink" => ["A", "B", "B", "T", "T"],
+            "capacity" => [4i64, 2, 2, 2, 3],
+            "cost" => [1.0, 2.0, 1.0, 3.0, 1.0]
+        )
+        .unwrap();
+        DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap()
+    }
+
+    fn supplies(nodes: &[&str], amounts: &[f64]) -> PyDataFrame {
+        PyDataFrame(df!("node" => nodes, "supply" => amounts).unwrap())
+    }
+
+    fn solve(graph: &DirectedGraph, nodes: &[&str], amounts: &[f64]) -> PyResult<DataFrame> {
+        graph
+            .min_cost_flow(
+                "capacity".into(),
+                "cost".into(),
+                Some(supplies(nodes, amounts)),
+                "node".into(),
+                "supply".into(),
+            )
+            .map(|df| df.0)
+    }
+
+    #[test]
+    fn routes_supply_at_minimum_cost() {
+        let df = solve(&network(), &["S", "T"], &[4.0, -4.0]).unwrap();
+        assert_eq!(df.height(), 5);
+        let total: f64 = df
+            .column("flow_cost")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .sum()
+            .unwrap();
+        assert_eq!(total, 13.0);
+
+        let sources = df.column("source").unwrap().str().unwrap().clone();
+        let targets = df.column("target").unwrap().str().unwrap().clone();
+        let flows = df.column("flow").unwrap().f64().unwrap().clone();
+        let mut balance: HashMap<String, f64> = HashMap::new();
+        for ((s, t), f) in sources
+            .into_no_null_iter()
+            .zip(targets.into_no_null_iter())
+            .zip(flows.into_no_null_iter())
+        {
+            *balance.entry(s.to_string()).or_default() -= f;
+            *balance.entry(t.to_string()).or_default() += f;
+        }
+        assert_eq!(balance["S"], -4.0);
+        assert_eq!(balance["T"], 4.0);
+        assert_eq!(balance["A"], 0.0);
+        assert_eq!(balance["B"], 0.0);
+    }
+
+    #[test]
+    fn infeasible_demand_is_an_error() {
+        assert!(solve(&network(), &["S", "T"], &[10.0, -10.0]).is_err());
+    }
+
+    #[test]
+    fn unbalanced_supplies_are_rejected() {
+        assert!(solve(&network(), &["S", "T"], &[4.0, -3.0]).is_err());
+    }
+
+    #[test]
+    fn negative_costs_are_used_when_profitable() {
+        let df = df!(
+            "source" => ["S", "S", "M"],
+            "sink" => ["T", "M", "T"],
+            "capacity" => [5.0, 5.0, 5.0],
+            "cost" => [1.0, 2.0, -4.0]
+        )
+        .unwrap();
+        let graph =
+            DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+        let result = solve(&graph, &["S", "T"], &[3.0, -3.0]).unwrap();
+        let flows: Vec<f64> = result
+            .column("flow")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(flows, vec![0.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn missing_columns_are_an_error() {
+        let result = network().min_cost_flow(
+            "capacity".into(),
+            "price".into(),
+            Some(supplies(&["S", "T"], &[1.0, -1.0])),
+            "node".into(),
+            "supply".into(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn null_costs_are_reported_by_row() {
+        let df = df!(
+            "source" => ["S", "A"],
+            "sink" => ["A", "T"],
+            "capacity" => [1.0, 1.0],
+            "cost" => [Some(1.0), None]
+        )
+        .unwrap();
+        let graph =
+            DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+        let err = solve(&graph, &["S", "T"], &[1.0, -1.0]).unwrap_err();
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let message = err.value(py).to_string();
+            assert!(message.starts_with("Invalid rows"), "{}", message);
+            assert!(message.ends_with("at rows [1]"), "{}", message);
+        });
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use petgraph::{Directed, Undirected};
+    use polars::prelude::*;
+    use polars_graph::expressions::cycle_lists;
+    use polars_graph::graph::NodeData;
+    use polars_graph::graph_core::GraphCore;
+    use polars_graph::ingest::{GraphOptions, MultiEdges};
+
+    fn frame() -> DataFrame {
+        df!(
+            "source" => ["a", "b", "b"],
+            "sink" => ["b", "a", "c"],
+            "weight" => [1.0, 2.0, 3.0]
+        )
+        .unwrap()
+    }
+
+    fn summed() -> GraphOptions {
+        GraphOptions {
+            multi_edges: MultiEdges::Sum,
+            ..GraphOptions::default()
+        }
+    }
+
+    #[test]
+    fn one_builder_serves_both_edge_types() {
+        let directed = GraphCore::<Directed>::build(
+            &frame(),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+            summed(),
+        )
+        .unwrap();
+        let undirected = GraphCore::<Undirected>::build(
+            &frame(),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+            summed(),
+        )
+        .unwrap();
+        assert_eq!(directed.graph.node_count(), 3);
+        assert_eq!(undirected.graph.node_count(), 3);
+        assert_eq!(directed.graph.edge_count(), 3);
+        // a to b and b to a are one undirected edge
+        assert_eq!(undirected.graph.edge_count(), 2);
+        let a = undirected.node_indices[&NodeData::String("a".into())];
+        let b = undirected.node_indices[&NodeData::String("b".into())];
+        let edge = undirected.graph.find_edge(a, b).unwrap();
+        assert_eq!(undirected.graph[edge], 3.0);
+    }
+
+    #[test]
+    fn mutations_go_through_the_core() {
+        let mut core = GraphCore::<Directed>::build(
+            &frame(),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+            GraphOptions::default(),
+        )
+        .unwrap();
+        let delta = df!("source" => ["c"], "sink" => ["d"], "weight" => [1.0]).unwrap();
+        core.add_edges(&delta).unwrap();
+        assert_eq!(core.graph.edge_count(), 4);
+        let gone = Series::new("node".into(), ["b"]);
+        assert_eq!(core.remove_nodes(&gone).unwrap(), 1);
+        assert_eq!(core.graph.edge_count(), 1);
+        assert_eq!(core.node_indices.len(), 3);
+    }
+
+    #[test]
+    fn expressions_share_the_builder_dtype_rules() {
+        let sources = Series::new("a".into(), [Some(1i32), Some(2), None]);
+        let sinks = Series::new("b".into(), [Some(2i64), Some(1), Some(3)]);
+        let cycles = cycle_lists(&sources, &sinks).unwrap();
+        assert_eq!(cycles.dtype(), &DataType::List(Box::new(DataType::Int32)));
+        assert_eq!(cycles.len(), 1);
+        let text = Series::new("b".into(), ["x", "y", "z"]);
+        assert!(cycle_lists(&sources, &text).is_err());
+    }
+}
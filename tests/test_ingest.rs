@@ -0,0 +1,261 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::node;
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, GraphError, UndirectedGraph};
+    use polars_graph::ingest::{read_edge_rows, GraphOptions, NullPolicy, WeightChecks};
+    use pyo3::PyResult;
+    use pyo3_polars::PyDataFrame;
+
+    /// row 1 lacks a source, row 3 a weight
+    fn gappy() -> DataFrame {
+        df!(
+            "source" => [Some("a"), None, Some("b"), Some("c")],
+            "sink" => [Some("b"), Some("x"), Some("c"), Some("d")],
+            "weight" => [Some(1.0), Some(2.0), Some(3.0), None]
+        )
+        .unwrap()
+    }
+
+    fn directed(df: DataFrame, null_policy: NullPolicy) -> PyResult<DirectedGraph> {
+        DirectedGraph::with_options(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+            GraphOptions {
+                null_policy,
+                ..GraphOptions::default()
+            },
+        )
+    }
+
+    fn sorted(series: &Series) -> Vec<String> {
+        let mut nodes: Vec<String> = series
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(String::from)
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    #[test]
+    fn nulls_are_rejected_by_default() {
+        let err = read_edge_rows(
+            &gappy(),
+            &"source".into(),
+            &"sink".into(),
+            Some("weight"),
+            &GraphOptions::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(&err, GraphError::InvalidRows(_, rows) if rows == &vec![1, 3]));
+        assert!(err.to_string().contains("at rows [1, 3]"));
+        assert!(directed(gappy(), NullPolicy::Error).is_err());
+    }
+
+    #[test]
+    fn drop_row_keeps_columns_aligned() {
+        let graph = directed(gappy(), NullPolicy::DropRow).unwrap();
+        let path = graph
+            .shortest_path(node("a").into(), node("c").into(), None, None, None, None)
+            .unwrap()
+            .0;
+        let weights: Vec<f64> = path
+            .column("weight")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(weights, vec![1.0, 3.0]);
+        assert!(graph.has_path(node("x").into(), node("d").into()).is_err());
+        assert_eq!(sorted(&graph.roots().unwrap().series().unwrap()), vec!["a"]);
+    }
+
+    #[test]
+    fn isolated_node_keeps_present_endpoints() {
+        let graph = directed(gappy(), NullPolicy::IsolatedNode).unwrap();
+        assert_eq!(
+            sorted(&graph.roots().unwrap().series().unwrap()),
+            vec!["a", "d", "x"]
+        );
+        assert_eq!(
+            sorted(&graph.leaves().unwrap().series().unwrap()),
+            vec!["c", "d", "x"]
+        );
+        assert!(!graph.has_path(node("c").into(), node("d").into()).unwrap());
+    }
+
+    #[test]
+    fn edge_attributes_follow_dropped_rows() {
+        let df = df!(
+            "source" => [Some("s"), None, Some("s"), Some("m")],
+            "sink" => [Some("t"), Some("t"), Some("m"), Some("t")],
+            "capacity" => [1.0, 9.0, 5.0, 5.0],
+            "cost" => [5.0, 0.0, 1.0, 1.0]
+        )
+        .unwrap();
+        let graph = DirectedGraph::with_options(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            None,
+            GraphOptions {
+                null_policy: NullPolicy::DropRow,
+                ..GraphOptions::default()
+            },
+        )
+        .unwrap();
+        let supplies = df!("node" => ["s", "t"], "supply" => [3.0, -3.0]).unwrap();
+        let flow = graph
+            .min_cost_flow(
+                "capacity".into(),
+                "cost".into(),
+                Some(PyDataFrame(supplies)),
+                "node".into(),
+                "supply".into(),
+            )
+            .unwrap()
+            .0;
+        let flows: Vec<f64> = flow
+            .column("flow")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(flows, vec![0.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn undirected_graph_shares_the_policy() {
+        assert!(
+            UndirectedGraph::new(PyDataFrame(gappy()), "source".into(), "sink".into(), None)
+                .is_err()
+        );
+        let graph = UndirectedGraph::with_options(
+            PyDataFrame(gappy()),
+            "source".into(),
+            "sink".into(),
+            None,
+            GraphOptions {
+                null_policy: NullPolicy::DropRow,
+                ..GraphOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(graph.has_path(node("a").into(), node("d").into()).unwrap());
+    }
+
+    #[test]
+    fn unknown_policy_is_an_argument_error() {
+        assert!(matches!(
+            NullPolicy::try_from("skip"),
+            Err(GraphError::InvalidArgument(_))
+        ));
+        assert_eq!(
+            NullPolicy::try_from("isolated_node").unwrap(),
+            NullPolicy::IsolatedNode
+        );
+    }
+
+    fn weights_of(df: DataFrame, options: &GraphOptions) -> Result<Vec<f64>, GraphError> {
+        let rows = read_edge_rows(
+            &df,
+            &"source".into(),
+            &"sink".into(),
+            Some("weight"),
+            options,
+        )?;
+        Ok(rows
+            .edges
+            .into_iter()
+            .map(|(_, _, weight)| weight)
+            .collect())
+    }
+
+    #[test]
+    fn numeric_weights_are_cast() {
+        let ints =
+            df!("source" => ["a", "b"], "sink" => ["b", "c"], "weight" => [2i32, 3]).unwrap();
+        assert_eq!(
+            weights_of(ints, &GraphOptions::default()).unwrap(),
+            vec![2.0, 3.0]
+        );
+        let floats = df!("source" => ["a"], "sink" => ["b"], "weight" => [1.5f32]).unwrap();
+        assert_eq!(
+            weights_of(floats, &GraphOptions::default()).unwrap(),
+            vec![1.5]
+        );
+        let decimal = Series::new("weight".into(), [1.25, 0.5])
+            .cast(&DataType::Decimal(Some(10), Some(2)))
+            .unwrap();
+        let decimals =
+            df!("source" => ["a", "b"], "sink" => ["b", "c"], "weight" => decimal).unwrap();
+        assert!(matches!(
+            decimals.column("weight").unwrap().dtype(),
+            DataType::Decimal(_, _)
+        ));
+        assert_eq!(
+            weights_of(decimals, &GraphOptions::default()).unwrap(),
+            vec![1.25, 0.5]
+        );
+        let text = df!("source" => ["a"], "sink" => ["b"], "weight" => ["1"]).unwrap();
+        assert!(matches!(
+            weights_of(text, &GraphOptions::default()),
+            Err(GraphError::InvalidDataType(_))
+        ));
+    }
+
+    #[test]
+    fn default_weight_fills_nulls() {
+        let options = GraphOptions {
+            default_weight: Some(7.0),
+            ..GraphOptions::default()
+        };
+        assert_eq!(
+            weights_of(
+                gappy()
+                    .drop_nulls::<String>(Some(&["source".into()]))
+                    .unwrap(),
+                &options
+            )
+            .unwrap(),
+            vec![1.0, 3.0, 7.0]
+        );
+    }
+
+    #[test]
+    fn weight_checks_report_bad_rows() {
+        let df = df!(
+            "source" => ["a", "b", "c", "d"],
+            "sink" => ["b", "c", "d", "e"],
+            "weight" => [1.0, -2.0, f64::NAN, f64::INFINITY]
+        )
+        .unwrap();
+        assert_eq!(
+            weights_of(df.clone(), &GraphOptions::default())
+                .unwrap()
+                .len(),
+            4
+        );
+        let checked = |names: &[&str]| {
+            let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+            GraphOptions {
+                weight_checks: WeightChecks::try_from(names.as_slice()).unwrap(),
+                ..GraphOptions::default()
+            }
+        };
+        let err = weights_of(df.clone(), &checked(&["negative"])).unwrap_err();
+        assert!(matches!(&err, GraphError::InvalidRows(_, rows) if rows == &vec![1]));
+        assert!(err.to_string().contains("negative weight"));
+        let err = weights_of(df.clone(), &checked(&["infinite", "nan"])).unwrap_err();
+        assert!(matches!(&err, GraphError::InvalidRows(_, rows) if rows == &vec![2]));
+        assert!(WeightChecks::try_from(["zero".to_string()].as_slice()).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, NodeData};
+    use polars_graph::ingest::{
+        collect_edge_input, EdgeInput, FrameInput, GraphOptions, KeySpec, WeightSpec,
+    };
+    use pyo3_polars::{PyDataFrame, PyExpr, PyLazyFrame};
+
+    fn wide() -> DataFrame {
+        df!(
+            "from" => [1i64, 2, 3],
+            "to" => [2i64, 3, 4],
+            "w" => [1.0, 2.0, 3.0],
+            "label" => ["x", "y", "z"],
+            "unused" => [0, 0, 0]
+        )
+        .unwrap()
+    }
+
+    fn names(input: &EdgeInput) -> Vec<String> {
+        input
+            .frame
+            .get_column_names()
+            .into_iter()
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    fn by_name(column: &str) -> KeySpec {
+        KeySpec::from(polars_graph::ingest::KeyColumns::from(column))
+    }
+
+    #[test]
+    fn lazy_frames_collect_only_needed_columns() {
+        let lazy = || FrameInput::Lazy(PyLazyFrame(wide().lazy()));
+        let weight = || Some(WeightSpec::Column("w".into()));
+        let input =
+            collect_edge_input(lazy(), by_name("from"), by_name("to"), weight(), None).unwrap();
+        assert_eq!(names(&input), vec!["from", "to", "w"]);
+        let input = collect_edge_input(
+            lazy(),
+            by_name("from"),
+            by_name("to"),
+            weight(),
+            Some(vec!["label".into()]),
+        )
+        .unwrap();
+        assert_eq!(names(&input), vec!["from", "to", "w", "label"]);
+    }
+
+    #[test]
+    fn eager_frames_with_plain_columns_are_untouched() {
+        let input = collect_edge_input(
+            FrameInput::Eager(PyDataFrame(wide())),
+            by_name("from"),
+            by_name("to"),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(input.frame.width(), 5);
+        assert!(input.weights_column.is_none());
+    }
+
+    #[test]
+    fn expressions_replace_the_columns_they_are_named_after() {
+        let input = collect_edge_input(
+            FrameInput::Eager(PyDataFrame(wide())),
+            KeySpec::Expr(PyExpr(col("from") * lit(10))),
+            KeySpec::Expr(PyExpr(col("to") * lit(10))),
+            Some(WeightSpec::Expr(PyExpr(
+                (col("w") * lit(2.0)).alias("cost"),
+            ))),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            names(&input),
+            vec!["from", "to", "cost", "w", "label", "unused"]
+        );
+        let graph = DirectedGraph::with_options(
+            PyDataFrame(input.frame),
+            input.sources_column,
+            input.sinks_column,
+            input.weights_column,
+            GraphOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            graph
+                .shortest_path_length(
+                    NodeData::Int(10).into(),
+                    NodeData::Int(40).into(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap(),
+            Some(12.0)
+        );
+    }
+
+    #[test]
+    fn filters_on_lazy_frames_apply_before_construction() {
+        let lazy = wide().lazy().filter(col("w").lt(lit(3.0)));
+        let input = collect_edge_input(
+            FrameInput::Lazy(PyLazyFrame(lazy)),
+            by_name("from"),
+            KeySpec::Exprs(vec![PyExpr(col("to"))]),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(input.frame.height(), 2);
+        assert_eq!(names(&input), vec!["from", "to"]);
+    }
+
+    #[test]
+    fn deltas_are_read_through_the_input_expressions() {
+        let input = collect_edge_input(
+            FrameInput::Eager(PyDataFrame(wide())),
+            KeySpec::Expr(PyExpr(col("from") + lit(100))),
+            KeySpec::Expr(PyExpr(col("to") + lit(100))),
+            Some(WeightSpec::Expr(PyExpr(
+                (col("w") * lit(2.0)).alias("cost"),
+            ))),
+            None,
+        )
+        .unwrap();
+        let mut graph = DirectedGraph::with_options(
+            PyDataFrame(input.frame),
+            input.sources_column,
+            input.sinks_column,
+            input.weights_column,
+            GraphOptions {
+                expressions: input.expressions,
+                ..GraphOptions::default()
+            },
+        )
+        .unwrap();
+        let length = |graph: &DirectedGraph, a: i64, b: i64| {
+            graph
+                .shortest_path_length(
+                    NodeData::Int(a).into(),
+                    NodeData::Int(b).into(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()
+        };
+
+        let delta = df!("from" => [4i64], "to" => [5i64], "w" => [1.0]).unwrap();
+        graph.add_edges(PyDataFrame(delta)).unwrap();
+        assert_eq!(length(&graph, 101, 105), Some(14.0));
+
+        let delta = df!("from" => [1i64], "to" => [2i64], "w" => [5.0]).unwrap();
+        graph.update_weights(PyDataFrame(delta), None).unwrap();
+        assert_eq!(length(&graph, 101, 102), Some(10.0));
+
+        // the weight expression is not needed to name an edge
+        let delta = df!("from" => [4i64], "to" => [5i64]).unwrap();
+        assert_eq!(graph.remove_edges(PyDataFrame(delta)).unwrap(), 1);
+        assert_eq!(length(&graph, 101, 105), None);
+    }
+}
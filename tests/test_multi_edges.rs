@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, GraphError, NodeData, UndirectedGraph};
+    use polars_graph::ingest::{read_edge_rows, GraphOptions, MultiEdges, SelfLoops};
+    use pyo3_polars::PyDataFrame;
+
+    /// a to b twice, and b to a once
+    fn repeated() -> DataFrame {
+        df!(
+            "source" => ["a", "a", "b"],
+            "sink" => ["b", "b", "a"],
+            "weight" => [1.0, 3.0, 5.0],
+            "label" => ["first", "second", "back"]
+        )
+        .unwrap()
+    }
+
+    fn options(multi_edges: &str, self_loops: &str) -> GraphOptions {
+        GraphOptions {
+            multi_edges: MultiEdges::try_from(multi_edges).unwrap(),
+            self_loops: SelfLoops::try_from(self_loops).unwrap(),
+            ..GraphOptions::default()
+        }
+    }
+
+    fn directed(df: DataFrame, multi_edges: &str) -> DirectedGraph {
+        DirectedGraph::with_options(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+            options(multi_edges, "keep"),
+        )
+        .unwrap()
+    }
+
+    fn weights(df: &DataFrame) -> Vec<f64> {
+        df.column("weight")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect()
+    }
+
+    #[test]
+    fn parallel_edges_are_combined_per_direction() {
+        let expected = [
+            ("keep", vec![1.0, 3.0, 5.0]),
+            ("first", vec![1.0, 5.0]),
+            ("sum", vec![4.0, 5.0]),
+            ("min", vec![1.0, 5.0]),
+            ("max", vec![3.0, 5.0]),
+            ("mean", vec![2.0, 5.0]),
+            ("count", vec![2.0, 1.0]),
+        ];
+        for (policy, expected) in expected {
+            let edges = directed(repeated(), policy).edges().unwrap().0;
+            assert_eq!(weights(&edges), expected, "{}", policy);
+        }
+    }
+
+    #[test]
+    fn merged_edges_keep_the_first_row_attributes() {
+        let edges = directed(repeated(), "sum").edges().unwrap().0;
+        let labels: Vec<&str> = edges
+            .column("label")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(labels, vec!["first", "back"]);
+    }
+
+    #[test]
+    fn numeric_attributes_are_combined_like_the_weights() {
+        let df = df!(
+            "source" => ["a", "a", "b"],
+            "sink" => ["b", "b", "a"],
+            "weight" => [1.0, 3.0, 5.0],
+            "cost" => [10i64, 30, 2]
+        )
+        .unwrap();
+        let expected = [
+            ("first", 10.0),
+            ("sum", 40.0),
+            ("min", 10.0),
+            ("max", 30.0),
+            ("mean", 20.0),
+            ("count", 40.0),
+        ];
+        for (policy, expected) in expected {
+            let graph = directed(df.clone(), policy);
+            let edges = graph.edges().unwrap().0;
+            let costs = edges
+                .column("cost")
+                .unwrap()
+                .cast(&DataType::Float64)
+                .unwrap();
+            assert_eq!(costs.f64().unwrap().get(0), Some(expected), "{}", policy);
+            let length = graph
+                .shortest_path_length(
+                    NodeData::String("a".into()).into(),
+                    NodeData::String("b".into()).into(),
+                    Some("cost".into()),
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(length, Some(expected), "{}", policy);
+        }
+        // a delta merged into an edge is folded into the values held so far
+        let mut graph = directed(df, "mean");
+        let delta =
+            df!("source" => ["a"], "sink" => ["b"], "weight" => [2.0], "cost" => [50i64]).unwrap();
+        graph.add_edges(PyDataFrame(delta)).unwrap();
+        let costs = graph.edges().unwrap().0.column("cost").unwrap().clone();
+        assert_eq!(costs.f64().unwrap().get(0), Some(30.0));
+    }
+
+    #[test]
+    fn undirected_edges_merge_in_either_order() {
+        let graph = UndirectedGraph::with_options(
+            PyDataFrame(repeated()),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+            options("sum", "keep"),
+        )
+        .unwrap();
+        assert_eq!(weights(&graph.edges().unwrap().0), vec![9.0]);
+    }
+
+    #[test]
+    fn deltas_merge_into_existing_edges() {
+        let mut graph = directed(repeated(), "mean");
+        let delta =
+            df!("source" => ["a", "c"], "sink" => ["b", "a"], "weight" => [8.0, 1.0]).unwrap();
+        graph.add_edges(PyDataFrame(delta)).unwrap();
+        // (1 + 3 + 8) / 3 for a to b
+        assert_eq!(weights(&graph.edges().unwrap().0), vec![4.0, 5.0, 1.0]);
+    }
+
+    #[test]
+    fn aggregated_weights_are_not_updated() {
+        let update = || df!("source" => ["a"], "sink" => ["b"], "weight" => [7.0]).unwrap();
+        for policy in ["count", "sum", "mean", "min", "max"] {
+            let mut graph = directed(repeated(), policy);
+            let before = weights(&graph.edges().unwrap().0);
+            let err = graph.update_weights(PyDataFrame(update()), None);
+            assert!(err.is_err(), "{}", policy);
+            assert_eq!(weights(&graph.edges().unwrap().0), before);
+        }
+        let mut graph = directed(repeated(), "first");
+        graph.update_weights(PyDataFrame(update()), None).unwrap();
+        assert_eq!(weights(&graph.edges().unwrap().0), vec![7.0, 5.0]);
+    }
+
+    #[test]
+    fn self_loops_follow_their_policy() {
+        let df = df!("source" => ["a", "b", "c"], "sink" => ["a", "c", "c"]).unwrap();
+        let err = read_edge_rows(
+            &df,
+            &"source".into(),
+            &"sink".into(),
+            None,
+            &options("keep", "error"),
+        )
+        .unwrap_err();
+        assert!(matches!(&err, GraphError::InvalidRows(_, rows) if rows == &vec![0, 2]));
+        let rows = read_edge_rows(
+            &df,
+            &"source".into(),
+            &"sink".into(),
+            None,
+            &options("keep", "drop"),
+        )
+        .unwrap();
+        assert_eq!(rows.edges.len(), 1);
+        assert_eq!(rows.nodes.len(), 3);
+        let kept = read_edge_rows(
+            &df,
+            &"source".into(),
+            &"sink".into(),
+            None,
+            &GraphOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(kept.edges.len(), 3);
+    }
+
+    #[test]
+    fn unknown_policies_are_argument_errors() {
+        assert!(matches!(
+            MultiEdges::try_from("median"),
+            Err(GraphError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            SelfLoops::try_from("skip"),
+            Err(GraphError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::node;
+    use polars::prelude::*;
+    use polars_graph::attributes::AttributeRows;
+    use polars_graph::graph::{DirectedGraph, UndirectedGraph};
+    use polars_graph::ingest::GraphOptions;
+    use pyo3_polars::{PyDataFrame, PySeries};
+
+    fn chain() -> DirectedGraph {
+        let df = df!(
+            "source" => ["a", "b"],
+            "sink" => ["b", "c"],
+            "weight" => [1.0, 2.0],
+            "label" => ["ab", "bc"]
+        )
+        .unwrap();
+        DirectedGraph::new(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap()
+    }
+
+    fn strings(df: &DataFrame, column: &str) -> Vec<Option<String>> {
+        df.column(column)
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.map(String::from))
+            .collect()
+    }
+
+    #[test]
+    fn added_edges_keep_their_attributes() {
+        let mut graph = chain();
+        assert!(!graph.has_path(node("c").into(), node("a").into()).unwrap());
+        graph.build_reachability_index();
+        let delta = df!(
+            "source" => ["c", "c"],
+            "sink" => ["a", "d"],
+            "weight" => [3i64, 4],
+            "label" => ["ca", "cd"]
+        )
+        .unwrap();
+        graph.add_edges(PyDataFrame(delta)).unwrap();
+        assert!(graph.has_path(node("a").into(), node("d").into()).unwrap());
+        let edges = graph.edges().unwrap().0;
+        assert_eq!(edges.height(), 4);
+        assert_eq!(
+            strings(&edges, "label"),
+            vec![
+                Some("ab".into()),
+                Some("bc".into()),
+                Some("ca".into()),
+                Some("cd".into())
+            ]
+        );
+        let wrong_dtype = df!("source" => [1i64], "sink" => [2i64], "weight" => [1.0]).unwrap();
+        assert!(graph.add_edges(PyDataFrame(wrong_dtype)).is_err());
+    }
+
+    #[test]
+    fn removed_indices_are_reused_without_stale_rows() {
+        let mut graph = chain();
+        let removed = graph
+            .remove_nodes(PySeries(Series::new("node".into(), ["b", "zz"])))
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(graph.edges().unwrap().0.height(), 0);
+        // the new node and edge take the vacant indices of b and its edges
+        let delta = df!("source" => ["x"], "sink" => ["c"], "weight" => [5.0]).unwrap();
+        graph.add_edges(PyDataFrame(delta)).unwrap();
+        let edges = graph.edges().unwrap().0;
+        assert_eq!(strings(&edges, "label"), vec![None]);
+        assert_eq!(strings(&edges, "source"), vec![Some("x".into())]);
+        assert!(graph.has_path(node("b").into(), node("c").into()).is_err());
+    }
+
+    #[test]
+    fn nodes_are_added_and_updated() {
+        let mut graph = chain();
+        let nodes = df!("source" => ["a", "z"], "color" => ["red", "blue"]).unwrap();
+        graph.add_nodes(PyDataFrame(nodes)).unwrap();
+        let frame = graph.nodes().unwrap().0;
+        assert_eq!(
+            strings(&frame, "node"),
+            vec![
+                Some("a".into()),
+                Some("b".into()),
+                Some("c".into()),
+                Some("z".into())
+            ]
+        );
+        assert_eq!(
+            strings(&frame, "color"),
+            vec![Some("red".into()), None, None, Some("blue".into())]
+        );
+    }
+
+    #[test]
+    fn edges_are_removed_by_endpoints() {
+        let mut graph = chain();
+        let gone = df!("source" => ["a", "c"], "sink" => ["b", "a"]).unwrap();
+        assert_eq!(graph.remove_edges(PyDataFrame(gone)).unwrap(), 1);
+        assert!(!graph.has_path(node("a").into(), node("c").into()).unwrap());
+
+        let df = df!("source" => ["a"], "sink" => ["b"]).unwrap();
+        let mut undirected =
+            UndirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+        let reversed = df!("source" => ["b"], "sink" => ["a"]).unwrap();
+        assert_eq!(undirected.remove_edges(PyDataFrame(reversed)).unwrap(), 1);
+        assert!(!undirected
+            .has_path(node("a").into(), node("b").into())
+            .unwrap());
+    }
+
+    #[test]
+    fn weights_are_updated_in_place() {
+        let mut graph = chain();
+        let update = df!("source" => ["a"], "sink" => ["b"], "weight" => [10.0]).unwrap();
+        graph.update_weights(PyDataFrame(update), None).unwrap();
+        assert_eq!(
+            graph
+                .shortest_path_length(
+                    node("a").into(),
+                    node("c").into(),
+                    Some("weight".into()),
+                    None,
+                    None,
+                    None
+                )
+                .unwrap(),
+            Some(12.0)
+        );
+        let missing =
+            df!("source" => ["a", "c"], "sink" => ["b", "a"], "weight" => [1.0, 1.0]).unwrap();
+        let err = graph.update_weights(PyDataFrame(missing), None);
+        assert!(err.is_err());
+        // the refused frame changed nothing
+        assert_eq!(
+            graph
+                .shortest_path_length(node("a").into(), node("c").into(), None, None, None, None)
+                .unwrap(),
+            Some(12.0)
+        );
+    }
+
+    #[test]
+    fn unweighted_graphs_need_a_weights_column() {
+        let df = df!("source" => ["a"], "sink" => ["b"]).unwrap();
+        let mut graph = DirectedGraph::with_options(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            None,
+            GraphOptions::default(),
+        )
+        .unwrap();
+        let update = df!("source" => ["a"], "sink" => ["b"], "cost" => [2.0]).unwrap();
+        assert!(graph
+            .update_weights(PyDataFrame(update.clone()), None)
+            .is_err());
+        graph
+            .update_weights(PyDataFrame(update), Some("cost".into()))
+            .unwrap();
+        assert_eq!(
+            graph
+                .shortest_path_length(node("a").into(), node("b").into(), None, None, None, None)
+                .unwrap(),
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn a_delta_that_does_not_fit_changes_nothing() {
+        let df = df!("source" => ["a"], "sink" => ["b"], "cost" => [1.5]).unwrap();
+        let mut graph =
+            DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+        let before = graph.edges().unwrap().0;
+        // "cheap" does not cast to the float cost column, so neither c nor d may appear
+        let delta = df!("source" => ["c"], "sink" => ["d"], "cost" => ["cheap"]).unwrap();
+        assert!(graph.add_edges(PyDataFrame(delta)).is_err());
+        assert!(graph.edges().unwrap().0.equals_missing(&before));
+        assert_eq!(graph.nodes().unwrap().0.height(), 2);
+        assert!(graph.has_path(node("c").into(), node("d").into()).is_err());
+
+        let nodes = df!("source" => ["a"], "rank" => [1i64]).unwrap();
+        graph.add_nodes(PyDataFrame(nodes)).unwrap();
+        let before = graph.nodes().unwrap().0;
+        let nodes = df!("source" => ["e"], "rank" => ["first"]).unwrap();
+        assert!(graph.add_nodes(PyDataFrame(nodes)).is_err());
+        assert!(graph.nodes().unwrap().0.equals_missing(&before));
+    }
+
+    #[test]
+    fn dead_attribute_rows_are_compacted() {
+        let mut rows = AttributeRows::default();
+        for round in 0..50i64 {
+            let delta = df!("round" => [round, round]).unwrap();
+            let staged = rows.stage(&delta).unwrap();
+            // index 0 is rewritten every round, index round + 1 keeps its row
+            rows.append(staged, [(0, 0), (round as usize + 1, 1)])
+                .unwrap();
+        }
+        assert!(rows.height() < 2 * 51, "{} rows held", rows.height());
+        let indices: Vec<usize> = (0..51).collect();
+        let rounds: Vec<Option<i64>> = rows
+            .column("round", &indices)
+            .unwrap()
+            .i64()
+            .unwrap()
+            .iter()
+            .collect();
+        assert_eq!(rounds[0], Some(49));
+        assert_eq!(rounds[1..], (0..50).map(Some).collect::<Vec<_>>()[..]);
+
+        rows.clear(0);
+        let indices = [0, 1];
+        let values = rows.column("round", &indices).unwrap();
+        assert_eq!(values.i64().unwrap().get(0), None);
+        assert_eq!(values.i64().unwrap().get(1), Some(0));
+    }
+}
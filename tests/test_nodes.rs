@@ -0,0 +1,232 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::node;
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, UndirectedGraph};
+    use polars_graph::ingest::GraphOptions;
+    use pyo3::PyResult;
+    use pyo3_polars::PyDataFrame;
+
+    fn edges() -> DataFrame {
+        df!(
+            "source" => ["s", "s", "m"],
+            "sink" => ["m", "t", "t"],
+            "capacity" => [5.0, 1.0, 5.0],
+            "cost" => [1.0, 5.0, 1.0]
+        )
+        .unwrap()
+    }
+
+    fn with_nodes(nodes: DataFrame, node_key: Option<&str>) -> PyResult<DirectedGraph> {
+        DirectedGraph::with_options(
+            PyDataFrame(edges()),
+            "source".into(),
+            "sink".into(),
+            None,
+            GraphOptions {
+                nodes: Some(nodes),
+                node_key: node_key.map(Into::into),
+                ..GraphOptions::default()
+            },
+        )
+    }
+
+    #[test]
+    fn declared_nodes_may_be_isolated() {
+        let nodes = df!("source" => ["s", "lonely"], "team" => ["ops", "dev"]).unwrap();
+        let graph = with_nodes(nodes, None).unwrap();
+        assert!(!graph
+            .has_path(node("s").into(), node("lonely").into())
+            .unwrap());
+        assert_eq!(graph.nodes().unwrap().0.height(), 4);
+    }
+
+    #[test]
+    fn nodes_carry_their_attributes() {
+        let nodes = df!("id" => ["t", "s"], "team" => ["ops", "dev"]).unwrap();
+        let df = with_nodes(nodes, Some("id")).unwrap().nodes().unwrap().0;
+        let names: Vec<&str> = df
+            .get_column_names()
+            .into_iter()
+            .map(|n| n.as_str())
+            .collect();
+        assert_eq!(names, vec!["node", "team"]);
+        let rows: Vec<(Option<&str>, Option<&str>)> = df
+            .column("node")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .zip(df.column("team").unwrap().str().unwrap())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("t"), Some("ops")),
+                (Some("s"), Some("dev")),
+                (Some("m"), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn without_a_node_frame_nodes_come_from_edges() {
+        let graph =
+            DirectedGraph::new(PyDataFrame(edges()), "source".into(), "sink".into(), None).unwrap();
+        let df = graph.nodes().unwrap().0;
+        assert_eq!(df.width(), 1);
+        assert_eq!(df.height(), 3);
+    }
+
+    #[test]
+    fn supplies_can_be_node_attributes() {
+        let nodes = df!("source" => ["s", "t"], "supply" => [3.0, -3.0]).unwrap();
+        let graph = with_nodes(nodes, None).unwrap();
+        let flow = graph
+            .min_cost_flow(
+                "capacity".into(),
+                "cost".into(),
+                None,
+                "node".into(),
+                "supply".into(),
+            )
+            .unwrap()
+            .0;
+        let flows: Vec<f64> = flow
+            .column("flow")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(flows, vec![3.0, 0.0, 3.0]);
+    }
+
+    /// `with_nodes` with the edge costs as weights
+    fn weighted_with_nodes(nodes: DataFrame) -> DirectedGraph {
+        DirectedGraph::with_options(
+            PyDataFrame(edges()),
+            "source".into(),
+            "sink".into(),
+            Some("cost".into()),
+            GraphOptions {
+                nodes: Some(nodes),
+                ..GraphOptions::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn node_attributes_steer_shortest_paths() {
+        let nodes = df!(
+            "source" => ["s", "m", "t"],
+            "toll" => [0.0, 10.0, 0.0],
+            "open" => [true, false, true],
+            "estimate" => [2.0, 1.0, 0.0]
+        )
+        .unwrap();
+        let graph = weighted_with_nodes(nodes);
+        let length = |heuristic: Option<&str>, toll: Option<&str>, open: Option<&str>| {
+            graph
+                .shortest_path_length(
+                    node("s").into(),
+                    node("t").into(),
+                    None,
+                    heuristic.map(Into::into),
+                    toll.map(Into::into),
+                    open.map(Into::into),
+                )
+                .unwrap()
+        };
+        assert_eq!(length(None, None, None), Some(2.0));
+        assert_eq!(length(Some("estimate"), None, None), Some(2.0));
+        // entering m costs its toll, so the direct edge wins
+        assert_eq!(length(None, Some("toll"), None), Some(5.0));
+        assert_eq!(length(Some("estimate"), None, Some("open")), Some(5.0));
+        let path = graph
+            .shortest_path(
+                node("s").into(),
+                node("t").into(),
+                None,
+                None,
+                None,
+                Some("open".into()),
+            )
+            .unwrap()
+            .0;
+        assert_eq!(path.height(), 1);
+    }
+
+    #[test]
+    fn node_cost_columns_are_checked() {
+        let nodes = df!(
+            "source" => ["s", "m", "t"],
+            "toll" => [0.0, -1.0, 0.0],
+            "team" => ["a", "b", "c"]
+        )
+        .unwrap();
+        let graph = with_nodes(nodes, None).unwrap();
+        let length = |toll: Option<&str>, filter: Option<&str>| {
+            graph.shortest_path_length(
+                node("s").into(),
+                node("t").into(),
+                None,
+                None,
+                toll.map(Into::into),
+                filter.map(Into::into),
+            )
+        };
+        assert!(length(Some("toll"), None).is_err());
+        assert!(length(None, Some("team")).is_err());
+        assert!(length(Some("missing"), None).is_err());
+        // nodes left out by the filter cannot end a path either
+        let nodes = df!("source" => ["s", "m", "t"], "open" => [true, true, false]).unwrap();
+        let graph = with_nodes(nodes, None).unwrap();
+        let length = graph
+            .shortest_path_length(
+                node("s").into(),
+                node("t").into(),
+                None,
+                None,
+                None,
+                Some("open".into()),
+            )
+            .unwrap();
+        assert_eq!(length, None);
+    }
+
+    #[test]
+    fn duplicate_or_null_keys_are_rejected() {
+        let nodes = df!("source" => ["s", "s"]).unwrap();
+        assert!(with_nodes(nodes, None).is_err());
+        let nodes = df!("source" => [Some("s"), None]).unwrap();
+        assert!(with_nodes(nodes, None).is_err());
+        let nodes = df!("source" => [1i64, 2]).unwrap();
+        assert!(with_nodes(nodes, None).is_err());
+    }
+
+    #[test]
+    fn undirected_graphs_accept_nodes_too() {
+        let nodes = df!("source" => ["x"], "weight" => [2.5]).unwrap();
+        let graph = UndirectedGraph::with_options(
+            PyDataFrame(edges()),
+            "source".into(),
+            "sink".into(),
+            None,
+            GraphOptions {
+                nodes: Some(nodes),
+                ..GraphOptions::default()
+            },
+        )
+        .unwrap();
+        let df = graph.nodes().unwrap().0;
+        assert_eq!(df.height(), 4);
+        assert_eq!(
+            df.column("weight").unwrap().f64().unwrap().get(0),
+            Some(2.5)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::node;
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, NodeData, UndirectedGraph};
+    use pyo3_polars::PyDataFrame;
+
+    /// A -> B -> C -> A is a cycle feeding D -> E, F stands apart with G
+    fn permissions() -> DirectedGraph {
+        let df = df!(
+            "source" => ["A", "B", "C", "C", "D", "F"],
+            "sink" => ["B", "C", "A", "D", "E", "G"]
+        )
+        .unwrap();
+        DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap()
+    }
+
+    fn reachable(
+        graph: &DirectedGraph,
+        left: &[Option<&str>],
+        right: &[Option<&str>],
+    ) -> Vec<Option<bool>> {
+        let pairs = df!("left" => left, "right" => right).unwrap();
+        graph
+            .reachable(PyDataFrame(pairs), "left".into(), "right".into())
+            .unwrap()
+            .0
+            .bool()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn has_path_follows_edge_direction() {
+        let graph = permissions();
+        assert!(graph.has_path(node("A").into(), node("E").into()).unwrap());
+        assert!(graph.has_path(node("C").into(), node("B").into()).unwrap());
+        assert!(!graph.has_path(node("E").into(), node("A").into()).unwrap());
+        assert!(!graph.has_path(node("A").into(), node("G").into()).unwrap());
+        assert!(graph.has_path(node("A").into(), node("Z").into()).is_err());
+    }
+
+    #[test]
+    fn bulk_pairs_are_row_aligned() {
+        let result = reachable(
+            &permissions(),
+            &[Some("B"), Some("E"), Some("D"), None, Some("F")],
+            &[Some("E"), Some("D"), Some("D"), Some("A"), Some("Z")],
+        );
+        assert_eq!(
+            result,
+            vec![Some(true), Some(false), Some(true), None, None]
+        );
+    }
+
+    #[test]
+    fn prebuilt_index_agrees_with_search() {
+        let sources: Vec<i64> = (0..30).flat_map(|i| [i, i]).collect();
+        let sinks: Vec<i64> = (0..30)
+            .flat_map(|i| [(i * 7 + 3) % 40, (i * 3 + 11) % 40])
+            .collect();
+        let df = df!("source" => sources, "sink" => sinks).unwrap();
+        let mut graph =
+            DirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+
+        let nodes: Vec<i64> = (0..40).collect();
+        let mut expected = Vec::new();
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+        for &a in &nodes {
+            for &b in &nodes {
+                let (na, nb) = (NodeData::Int(a), NodeData::Int(b));
+                if let Ok(found) = graph.has_path(na.into(), nb.into()) {
+                    expected.push(Some(found));
+                    left.push(a);
+                    right.push(b);
+                }
+            }
+        }
+
+        graph.build_reachability_index();
+        let pairs = df!("left" => &left, "right" => &right).unwrap();
+        let result: Vec<Option<bool>> = graph
+            .reachable(PyDataFrame(pairs), "left".into(), "right".into())
+            .unwrap()
+            .0
+            .bool()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(result, expected);
+        for ((&a, &b), found) in left.iter().zip(&right).zip(&expected) {
+            let indexed = graph
+                .has_path(NodeData::Int(a).into(), NodeData::Int(b).into())
+                .unwrap();
+            assert_eq!(Some(indexed), *found);
+        }
+    }
+
+    #[test]
+    fn undirected_reachability_ignores_direction() {
+        let df = df!("source" => ["A", "C"], "sink" => ["B", "D"]).unwrap();
+        let mut graph =
+            UndirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap();
+        graph.build_reachability_index();
+        assert!(graph.has_path(node("B").into(), node("A").into()).unwrap());
+        assert!(!graph.has_path(node("A").into(), node("D").into()).unwrap());
+    }
+}
@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, NodeData, UndirectedGraph};
+    use pyo3_polars::PyDataFrame;
+
+    /// 40 nodes with two pseudo random out edges each and uneven weights
+    fn network() -> PyDataFrame {
+        let sources: Vec<i64> = (0..40).flat_map(|i| [i, i]).collect();
+        let sinks: Vec<i64> = (0..40)
+            .flat_map(|i| [(i * 7 + 3) % 40, (i * 13 + 5) % 40])
+            .collect();
+        let weights: Vec<f64> = (0..80).map(|i| ((i * 37) % 11 + 1) as f64).collect();
+        PyDataFrame(df!("source" => sources, "sink" => sinks, "weight" => weights).unwrap())
+    }
+
+    fn directed() -> DirectedGraph {
+        DirectedGraph::new(
+            network(),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap()
+    }
+
+    /// plain dijkstra lengths from every source, used as the reference answer
+    fn reference(graph: &DirectedGraph) -> Vec<(i64, i64, Option<f64>)> {
+        let mut expected = Vec::new();
+        for a in 0..40i64 {
+            let pairs =
+                df!("left" => vec![a; 40], "right" => (0..40i64).collect::<Vec<_>>()).unwrap();
+            let lengths = graph
+                .shortest_path_lengths(PyDataFrame(pairs), "left".into(), "right".into(), None)
+                .unwrap()
+                .0;
+            for (b, length) in lengths.f64().unwrap().into_iter().enumerate() {
+                expected.push((a, b as i64, length));
+            }
+        }
+        expected
+    }
+
+    #[test]
+    fn bidirectional_search_matches_full_search() {
+        let graph = directed();
+        for (a, b, expected) in reference(&graph) {
+            let found = graph
+                .shortest_path_length(
+                    NodeData::Int(a).into(),
+                    NodeData::Int(b).into(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(found, expected, "pair {} -> {}", a, b);
+        }
+    }
+
+    #[test]
+    fn path_steps_add_up_to_length() {
+        let graph = directed();
+        for (a, b, expected) in reference(&graph).into_iter().step_by(7) {
+            let Some(expected) = expected else { continue };
+            let path = graph
+                .shortest_path(
+                    NodeData::Int(a).into(),
+                    NodeData::Int(b).into(),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()
+                .0;
+            let sources: Vec<i64> = path
+                .column("source")
+                .unwrap()
+                .i64()
+                .unwrap()
+                .into_no_null_iter()
+                .collect();
+            let targets: Vec<i64> = path
+                .column("target")
+                .unwrap()
+                .i64()
+                .unwrap()
+                .into_no_null_iter()
+                .collect();
+            let weights = path
+                .column("weight")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .sum()
+                .unwrap_or(0.0);
+            assert_eq!(weights, expected);
+            if a != b {
+                assert_eq!(sources[0], a);
+                assert_eq!(*targets.last().unwrap(), b);
+            }
+            for step in 1..sources.len() {
+                assert_eq!(sources[step], targets[step - 1]);
+            }
+        }
+    }
+
+    #[test]
+    fn batched_lengths_are_row_aligned() {
+        let df = df!(
+            "source" => ["A", "B", "A"],
+            "sink" => ["B", "C", "C"],
+            "weight" => [1.0, 1.0, 5.0]
+        )
+        .unwrap();
+        let graph = DirectedGraph::new(
+            PyDataFrame(df),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap();
+        let pairs = df!(
+            "from" => [Some("A"), Some("C"), None, Some("A"), Some("B")],
+            "to" => [Some("C"), Some("A"), Some("A"), Some("Z"), Some("C")]
+        )
+        .unwrap();
+        let lengths: Vec<Option<f64>> = graph
+            .shortest_path_lengths(PyDataFrame(pairs), "from".into(), "to".into(), None)
+            .unwrap()
+            .0
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(lengths, vec![Some(2.0), None, None, None, Some(1.0)]);
+        assert!(graph
+            .shortest_path(
+                NodeData::String("C".into()).into(),
+                NodeData::String("A".into()).into(),
+                None,
+                None,
+                None,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn undirected_paths_run_both_ways() {
+        let graph = UndirectedGraph::new(
+            network(),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap();
+        let forward = graph
+            .shortest_path_length(
+                NodeData::Int(3).into(),
+                NodeData::Int(17).into(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let backward = graph
+            .shortest_path_length(
+                NodeData::Int(17).into(),
+                NodeData::Int(3).into(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(forward.is_some());
+        assert_eq!(forward, backward);
+    }
+}
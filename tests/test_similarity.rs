@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use polars_graph::graph::UndirectedGraph;
+    use pyo3_polars::PyDataFrame;
+
+    /// A and B share neighbours C and D, D also links to E
+    fn friends() -> UndirectedGraph {
+        let df = df!(
+            "source" => ["A", "A", "B", "B", "D"],
+            "sink" => ["C", "D", "C", "D", "E"]
+        )
+        .unwrap();
+        UndirectedGraph::new(PyDataFrame(df), "source".into(), "sink".into(), None).unwrap()
+    }
+
+    fn values(df: &DataFrame, name: &str) -> Vec<Option<f64>> {
+        df.column(name)
+            .unwrap()
+            .cast(&DataType::Float64)
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn scores_candidate_pairs_row_aligned() {
+        let pairs = df!("u" => [Some("A"), Some("A"), None, Some("A")], "v" => [Some("B"), Some("E"), Some("B"), Some("Z")]).unwrap();
+        let df = friends()
+            .link_prediction(Some(PyDataFrame(pairs)), "u".into(), "v".into())
+            .unwrap()
+            .0;
+
+        assert_eq!(df.height(), 4);
+        assert_eq!(
+            values(&df, "common_neighbors"),
+            vec![Some(2.0), Some(1.0), None, None]
+        );
+        assert_eq!(values(&df, "jaccard")[0], Some(1.0));
+        assert_eq!(values(&df, "preferential_attachment")[0], Some(4.0));
+
+        // C has degree 2 and D has degree 3
+        let adamic_adar = values(&df, "adamic_adar")[0].unwrap();
+        assert!((adamic_adar - (1.0 / 2f64.ln() + 1.0 / 3f64.ln())).abs() < 1e-12);
+        let resource_allocation = values(&df, "resource_allocation")[0].unwrap();
+        assert!((resource_allocation - (0.5 + 1.0 / 3.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn keeps_input_pair_columns() {
+        let pairs = df!("u" => ["A"], "v" => ["B"]).unwrap();
+        let df = friends()
+            .link_prediction(Some(PyDataFrame(pairs)), "u".into(), "v".into())
+            .unwrap()
+            .0;
+        assert_eq!(
+            df.get_column_names_str(),
+            vec![
+                "u",
+                "v",
+                "common_neighbors",
+                "jaccard",
+                "adamic_adar",
+                "resource_allocation",
+                "preferential_attachment"
+            ]
+        );
+    }
+
+    #[test]
+    fn enumerates_non_adjacent_two_hop_pairs() {
+        let df = friends()
+            .link_prediction(None, "left".into(), "right".into())
+            .unwrap()
+            .0;
+        let mut pairs: Vec<(String, String)> = df
+            .column("left")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .zip(
+                df.column("right")
+                    .unwrap()
+                    .str()
+                    .unwrap()
+                    .into_no_null_iter(),
+            )
+            .map(|(a, b)| {
+                let (a, b) = if a < b { (a, b) } else { (b, a) };
+                (a.to_string(), b.to_string())
+            })
+            .collect();
+        pairs.sort();
+        let expected: Vec<(String, String)> = [("A", "B"), ("A", "E"), ("B", "E"), ("C", "D")]
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        assert_eq!(pairs, expected);
+    }
+}
@@ -0,0 +1,121 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{edges, node};
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, UndirectedGraph};
+    use pyo3_polars::PySeries;
+    use std::collections::HashMap;
+
+    /// A -> B -> D -> E, A -> C -> D
+    fn lineage() -> DirectedGraph {
+        DirectedGraph::new(
+            edges(&["A", "A", "B", "C", "D"], &["B", "C", "D", "D", "E"]),
+            "source".into(),
+            "sink".into(),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn strings(df: &DataFrame, name: &str) -> Vec<Option<String>> {
+        df.column(name)
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect()
+    }
+
+    fn depth_by_node(df: &DataFrame) -> HashMap<String, u32> {
+        strings(df, "node")
+            .into_iter()
+            .zip(
+                df.column("depth")
+                    .unwrap()
+                    .u32()
+                    .unwrap()
+                    .into_no_null_iter(),
+            )
+            .map(|(n, d)| (n.unwrap(), d))
+            .collect()
+    }
+
+    #[test]
+    fn bfs_reports_depth_parent_and_order() {
+        let df = lineage().bfs(node("A").into(), None).unwrap().0;
+        let depths = depth_by_node(&df);
+        assert_eq!(depths["A"], 0);
+        assert_eq!(depths["B"], 1);
+        assert_eq!(depths["C"], 1);
+        assert_eq!(depths["D"], 2);
+        assert_eq!(depths["E"], 3);
+
+        let parents = strings(&df, "parent");
+        assert_eq!(parents[0], None);
+        assert_eq!(parents[4], Some("D".to_string()));
+        let order: Vec<u32> = df
+            .column("order")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn bfs_stops_at_max_depth() {
+        let df = lineage().bfs(node("A").into(), Some(1)).unwrap().0;
+        assert_eq!(df.height(), 3);
+    }
+
+    #[test]
+    fn dfs_pre_and_post_order() {
+        let pre = strings(&lineage().dfs(node("A").into(), "pre").unwrap().0, "node");
+        let post = strings(&lineage().dfs(node("A").into(), "post").unwrap().0, "node");
+        assert_eq!(pre.len(), 5);
+        assert_eq!(post.len(), 5);
+        assert_eq!(pre[0], Some("A".to_string()));
+        assert_eq!(post[0], Some("E".to_string()));
+        assert_eq!(post[4], Some("A".to_string()));
+
+        let position = |order: &[Option<String>], name: &str| {
+            order
+                .iter()
+                .position(|n| n.as_deref() == Some(name))
+                .unwrap()
+        };
+        assert!(position(&pre, "D") < position(&pre, "E"));
+        assert!(position(&post, "E") < position(&post, "D"));
+    }
+
+    #[test]
+    fn dfs_rejects_unknown_order() {
+        assert!(lineage().dfs(node("A").into(), "level").is_err());
+    }
+
+    #[test]
+    fn multi_source_bfs_labels_nearest_source() {
+        let path = UndirectedGraph::new(
+            edges(&["A", "B", "C", "D"], &["B", "C", "D", "E"]),
+            "source".into(),
+            "sink".into(),
+            None,
+        )
+        .unwrap();
+        let sources = PySeries(Series::new("sources".into(), ["A", "E"]));
+        let df = path.multi_source_bfs(sources, None).unwrap().0;
+        let nearest: HashMap<String, String> = strings(&df, "node")
+            .into_iter()
+            .zip(strings(&df, "source"))
+            .map(|(n, s)| (n.unwrap(), s.unwrap()))
+            .collect();
+        assert_eq!(nearest["B"], "A");
+        assert_eq!(nearest["C"], "A");
+        assert_eq!(nearest["D"], "E");
+        assert_eq!(depth_by_node(&df)["D"], 1);
+    }
+}
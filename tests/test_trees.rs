@@ -0,0 +1,108 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::directed;
+    use polars::prelude::*;
+    use polars_graph::graph::DirectedGraph;
+    use std::collections::HashMap;
+
+    /// ceo over cto and cfo, the cto over two engineers and one of them over an intern
+    fn org_chart() -> DirectedGraph {
+        directed(
+            &["ceo", "ceo", "cto", "cto", "eng1"],
+            &["cto", "cfo", "eng1", "eng2", "intern"],
+        )
+    }
+
+    fn sorted(series: &Series) -> Vec<String> {
+        let mut nodes: Vec<String> = series
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(String::from)
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    fn by_node(df: &DataFrame, column: &str) -> HashMap<String, u32> {
+        let nodes = df.column("node").unwrap().str().unwrap().clone();
+        let values = df.column(column).unwrap().u32().unwrap().clone();
+        nodes
+            .into_no_null_iter()
+            .zip(values.into_no_null_iter())
+            .map(|(n, v)| (n.to_string(), v))
+            .collect()
+    }
+
+    mod shape {
+        use super::*;
+
+        #[test]
+        fn org_chart_is_a_tree() {
+            let graph = org_chart();
+            assert!(graph.is_tree());
+            assert!(graph.is_forest());
+        }
+
+        #[test]
+        fn two_roots_make_a_forest() {
+            let graph = directed(&["a", "x"], &["b", "y"]);
+            assert!(!graph.is_tree());
+            assert!(graph.is_forest());
+        }
+
+        #[test]
+        fn shared_child_or_cycle_is_not_a_forest() {
+            assert!(!directed(&["a", "b"], &["c", "c"]).is_forest());
+            assert!(!directed(&["a", "b"], &["b", "a"]).is_forest());
+            assert!(!directed(&["a"], &["a"]).is_forest());
+        }
+
+        #[test]
+        fn roots_and_leaves() {
+            let graph = org_chart();
+            assert_eq!(
+                sorted(&graph.roots().unwrap().series().unwrap()),
+                vec!["ceo"]
+            );
+            assert_eq!(
+                sorted(&graph.leaves().unwrap().series().unwrap()),
+                vec!["cfo", "eng2", "intern"]
+            );
+        }
+    }
+
+    mod measures {
+        use super::*;
+
+        #[test]
+        fn depth_below_the_root() {
+            let df = org_chart().depth().unwrap().0;
+            let depth = by_node(&df, "depth");
+            assert_eq!(depth["ceo"], 0);
+            assert_eq!(depth["cfo"], 1);
+            assert_eq!(depth["eng2"], 2);
+            assert_eq!(depth["intern"], 3);
+            assert_eq!(df.height(), 6);
+        }
+
+        #[test]
+        fn subtree_sizes_include_the_node() {
+            let df = org_chart().subtree_sizes().unwrap().0;
+            let size = by_node(&df, "size");
+            assert_eq!(size["ceo"], 6);
+            assert_eq!(size["cto"], 4);
+            assert_eq!(size["eng1"], 2);
+            assert_eq!(size["cfo"], 1);
+        }
+
+        #[test]
+        fn depth_of_a_non_forest_is_an_error() {
+            let graph = directed(&["a", "b"], &["c", "c"]);
+            assert!(graph.depth().is_err());
+            assert!(graph.subtree_sizes().is_err());
+        }
+    }
+}
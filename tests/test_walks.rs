@@ -0,0 +1,129 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::weighted_edges;
+    use polars::prelude::*;
+    use polars_graph::graph::{DirectedGraph, UndirectedGraph};
+    use pyo3_polars::PySeries;
+
+    fn walks(df: &DataFrame) -> Vec<Vec<String>> {
+        df.column("walk")
+            .unwrap()
+            .list()
+            .unwrap()
+            .into_no_null_iter()
+            .map(|walk| {
+                walk.str()
+                    .unwrap()
+                    .into_no_null_iter()
+                    .map(|s| s.to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn ring() -> UndirectedGraph {
+        UndirectedGraph::new(
+            weighted_edges(
+                &["A", "B", "C", "D"],
+                &["B", "C", "D", "A"],
+                &[1.0, 2.0, 3.0, 4.0],
+            ),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap()
+    }
+
+    fn starts(values: &[&str]) -> Option<PySeries> {
+        Some(PySeries(Series::new("start".into(), values)))
+    }
+
+    #[test]
+    fn same_seed_gives_same_walks() {
+        let first = ring()
+            .random_walks(None, 10, 3, 0.5, 2.0, Some(7), None)
+            .unwrap()
+            .0;
+        let second = ring()
+            .random_walks(None, 10, 3, 0.5, 2.0, Some(7), None)
+            .unwrap()
+            .0;
+        assert_eq!(walks(&first), walks(&second));
+        assert_eq!(first.height(), 12);
+    }
+
+    #[test]
+    fn walks_follow_edges_from_their_start() {
+        let df = ring()
+            .random_walks(starts(&["B"]), 6, 4, 1.0, 1.0, Some(1), None)
+            .unwrap()
+            .0;
+        let ids: Vec<u32> = df
+            .column("walk_id")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(ids, vec![0, 1, 2, 3]);
+        for walk in walks(&df) {
+            assert_eq!(walk.len(), 6);
+            assert_eq!(walk[0], "B");
+            for step in walk.windows(2) {
+                assert_ne!(step[0], step[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn low_return_parameter_backtracks() {
+        let path = UndirectedGraph::new(
+            weighted_edges(&["A", "B"], &["B", "C"], &[1.0, 1.0]),
+            "source".into(),
+            "sink".into(),
+            None,
+        )
+        .unwrap();
+        let df = path
+            .random_walks(starts(&["A"]), 7, 5, 1e-12, 1.0, Some(3), None)
+            .unwrap()
+            .0;
+        for walk in walks(&df) {
+            assert_eq!(walk, vec!["A", "B", "A", "B", "A", "B", "A"]);
+        }
+    }
+
+    #[test]
+    fn directed_walks_stop_at_sinks_and_skip_zero_weights() {
+        let graph = DirectedGraph::new(
+            weighted_edges(&["A", "A", "B"], &["B", "C", "D"], &[1.0, 0.0, 1.0]),
+            "source".into(),
+            "sink".into(),
+            Some("weight".into()),
+        )
+        .unwrap();
+        let df = graph
+            .random_walks(starts(&["A"]), 10, 5, 1.0, 1.0, Some(11), None)
+            .unwrap()
+            .0;
+        for walk in walks(&df) {
+            assert_eq!(walk, vec!["A", "B", "D"]);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(ring()
+            .random_walks(None, 5, 1, 0.0, 1.0, Some(1), None)
+            .is_err());
+        assert!(ring()
+            .random_walks(None, 0, 1, 1.0, 1.0, Some(1), None)
+            .is_err());
+        assert!(ring()
+            .random_walks(starts(&["Z"]), 5, 1, 1.0, 1.0, Some(1), None)
+            .is_err());
+    }
+}